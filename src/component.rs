@@ -1,20 +1,61 @@
 pub mod simple;
 
-use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, MouseButton, MouseEventKind};
+use crossterm::event::{
+    Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind,
+};
 use tui::{
     buffer::Buffer,
-    layout::Rect,
-    style::{Color, Style},
+    layout::{Direction, Rect},
+    style::{Color, Modifier, Style},
     widgets::{Block, BorderType, Borders, StatefulWidget, Widget},
 };
 
-use crate::{Border, Focus, ResizeError};
+use crate::{Border, EventResult, Focus, MenuItem, ResizeError};
 
 pub trait ComponentWidget {
     // Handles a mouse event, returns true if component needs to be redrawn
     fn handle_mouse(&mut self, x: u16, y: u16, kind: MouseEventKind) -> bool;
-    // Handles a key event, returns true if component needs to be redrawn
-    fn handle_key(&mut self, e: KeyEvent) -> bool;
+    /// Handles a drag or release outside of the widget after a mouse down
+    /// inside it, with the position relative to the widget, returns true if
+    /// component needs to be redrawn
+    fn handle_mouse_captured(&mut self, _x: i32, _y: i32, _kind: MouseEventKind) -> bool {
+        false
+    }
+    /// Handles a click after the mouse down, with the number of clicks made in
+    /// quick succession (2 for a double click), returns true if component
+    /// needs to be redrawn
+    fn handle_click(&mut self, _x: u16, _y: u16, _button: MouseButton, _count: u8) -> bool {
+        false
+    }
+    /// Handles a key event, returning if the key was used and if the
+    /// component needs to be redrawn
+    fn handle_key(&mut self, e: KeyEvent) -> EventResult;
+    /// Handles the mouse entering or leaving the component, returns true if
+    /// component needs to be redrawn
+    fn handle_hover(&mut self, _hovered: bool) -> bool {
+        false
+    }
+    /// Handles pasted text, returning if the text was used and if the
+    /// component needs to be redrawn
+    fn handle_paste(&mut self, _text: &str) -> EventResult {
+        EventResult::Ignored
+    }
+    /// Returns the context menu items for a right click at the position in
+    /// the widget, or none if the click was on the border
+    fn get_menu_items(&self, _position: Option<(u16, u16)>) -> Vec<MenuItem> {
+        Vec::new()
+    }
+    /// Handles the action of a context menu item chosen from this widget,
+    /// returning if the action was used and if the component needs to be
+    /// redrawn
+    fn handle_menu(&mut self, _action: &str) -> EventResult {
+        EventResult::Ignored
+    }
+    /// Handles the terminal gaining or losing focus, returns true if component
+    /// needs to be redrawn
+    fn handle_terminal_focus(&mut self, _focused: bool) -> bool {
+        false
+    }
     // Handles a general update, returns true if component needs to be redrawn
     fn handle_update(&mut self) -> bool;
     /// Resizes this component to fit in the new size
@@ -26,23 +67,57 @@ pub trait ComponentWidget {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
 }
 
+/// Determines which component receives scroll wheel events
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScrollPolicy {
+    /// Scroll events only go to the component if it has focus
+    Focused,
+    /// Scroll events go to the component under the mouse without changing
+    /// focus
+    Hovered,
+    /// Scroll events focus the component under the mouse, unless the
+    /// modifiers are held to scroll it without focusing
+    FocusHovered(KeyModifiers),
+}
+
+/// Determines how the mouse gives focus to a component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FocusPolicy {
+    /// Clicking focuses the component and the click is sent to the widget
+    ClickThrough,
+    /// The first click only focuses the component, later clicks are sent to
+    /// the widget
+    ClickToFocus,
+    /// Moving the mouse over the component focuses it
+    FollowMouse,
+}
+
 pub trait ComponentBase {
+    /// Handles a mouse event, or the mouse leaving and focus being lost if
+    /// none, scroll events are left to `handle_scroll`
     fn handle_mouse(&mut self, x: u16, y: u16, kind: Option<MouseEventKind>);
-    fn handle_key(&mut self, e: KeyEvent) -> Option<Border>;
+    /// Handles the mouse moving off of this component, clearing any hover
+    /// state without changing focus
+    fn handle_mouse_leave(&mut self);
+    /// Handles a drag or release after a mouse down in this component,
+    /// wherever the mouse is, so the position may be outside of it
+    fn handle_mouse_captured(&mut self, x: i32, y: i32, kind: MouseEventKind);
+    /// Handles a click with the number of clicks made in quick succession
+    fn handle_click(&mut self, x: u16, y: u16, button: MouseButton, count: u8);
+    /// Handles a scroll wheel event with the modifiers held while scrolling
+    fn handle_scroll(&mut self, x: u16, y: u16, kind: MouseEventKind, modifiers: KeyModifiers);
+    /// Handles a key event, returning ignored if no focused component used
+    /// the key so that the enclosing container can handle it instead
+    fn handle_key(&mut self, e: KeyEvent) -> EventResult;
+    /// Handles pasted text, sending it to the focused component
+    fn handle_paste(&mut self, text: &str) -> EventResult;
+    /// Handles the terminal gaining or losing focus, sending it to every
+    /// component
+    fn handle_terminal_focus(&mut self, focused: bool);
     fn handle_update(&mut self);
 
-    fn handle_input(&mut self, event: CrosstermEvent) -> Option<Border> {
-        match event {
-            CrosstermEvent::Key(key) => self.handle_key(key),
-            CrosstermEvent::Mouse(event) => {
-                self.handle_mouse(event.column, event.row, Some(event.kind));
-                None
-            }
-            CrosstermEvent::Resize(_, _)
-            | CrosstermEvent::FocusGained
-            | CrosstermEvent::FocusLost
-            | CrosstermEvent::Paste(_) => None,
-        }
+    fn handle_input(&mut self, event: CrosstermEvent) -> EventResult {
+        handle_input_default(self, event)
     }
 
     /// Indicates that all sub-components need to be redrawn
@@ -59,6 +134,13 @@ pub trait ComponentBase {
     fn is_fixed_width(&self) -> bool;
     fn is_fixed_height(&self) -> bool;
 
+    /// Returns the smallest and largest size this component can be resized to
+    /// along the direction
+    fn get_size_range(&self, direction: Direction) -> (u16, u16);
+
+    /// Returns if this component (or any child component) can take focus
+    fn is_focusable(&self) -> bool;
+
     /// Renders the component to the area specified on the buffer, marking the
     /// component as clean when done
     fn render(&mut self, area: Rect, buf: &mut Buffer);
@@ -70,6 +152,39 @@ pub trait ComponentBase {
 
     /// Returns which border the x,y position is on, or none if not on a border
     fn get_border(&self, x: u16, y: u16) -> Option<Border>;
+
+    /// Returns which border the x,y position is on, only checking the left and
+    /// right borders for horizontal and the top and bottom for vertical
+    fn get_border_along(&self, x: u16, y: u16, direction: Direction) -> Option<Border>;
+}
+
+/// Sends an input event to the matching handler of the component
+pub(crate) fn handle_input_default<T>(base: &mut T, event: CrosstermEvent) -> EventResult
+where
+    T: ComponentBase + ?Sized,
+{
+    match event {
+        CrosstermEvent::Key(key) => base.handle_key(key),
+        CrosstermEvent::Mouse(event) => {
+            match event.kind {
+                MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+                    base.handle_scroll(event.column, event.row, event.kind, event.modifiers)
+                }
+                kind => base.handle_mouse(event.column, event.row, Some(kind)),
+            }
+            EventResult::Consumed
+        }
+        CrosstermEvent::Paste(text) => base.handle_paste(&text),
+        CrosstermEvent::FocusGained => {
+            base.handle_terminal_focus(true);
+            EventResult::Redraw
+        }
+        CrosstermEvent::FocusLost => {
+            base.handle_terminal_focus(false);
+            EventResult::Redraw
+        }
+        CrosstermEvent::Resize(_, _) => EventResult::Ignored,
+    }
 }
 
 pub struct Component {
@@ -78,9 +193,24 @@ pub struct Component {
     height: u16,
     fixed_width: bool,
     fixed_height: bool,
+    min_width: Option<u16>,
+    min_height: Option<u16>,
+    max_width: Option<u16>,
+    max_height: Option<u16>,
+    adjustable: bool,
     border_width: u16,
     invalidated: bool,
     focus: Focus,
+    focusable: bool,
+    disabled: bool,
+    terminal_focused: bool,
+    hovered: bool,
+    hover_border: Option<Border>,
+    hover_highlight: bool,
+    scroll_policy: ScrollPolicy,
+    captured: bool,
+    focus_policy: FocusPolicy,
+    focus_click: bool,
     widget: Box<dyn ComponentWidget>,
 }
 
@@ -92,9 +222,24 @@ impl Component {
             height: 0,
             fixed_width: false,
             fixed_height: false,
+            min_width: None,
+            min_height: None,
+            max_width: None,
+            max_height: None,
+            adjustable: false,
             border_width,
             invalidated: true,
             focus: Focus::None,
+            focusable: true,
+            disabled: false,
+            terminal_focused: true,
+            hovered: false,
+            hover_border: None,
+            hover_highlight: true,
+            scroll_policy: ScrollPolicy::Hovered,
+            captured: false,
+            focus_policy: FocusPolicy::ClickThrough,
+            focus_click: false,
             widget,
         }
     }
@@ -110,6 +255,110 @@ impl Component {
         }
     }
 
+    /// Returns if the terminal this component is drawn in has focus
+    pub fn is_terminal_focused(&self) -> bool {
+        self.terminal_focused
+    }
+
+    /// Returns if the mouse is over this component
+    pub fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    /// Returns the border the mouse is over, if any
+    pub fn get_hover_border(&self) -> Option<Border> {
+        self.hover_border.clone()
+    }
+
+    pub fn get_hover_highlight(&self) -> bool {
+        self.hover_highlight
+    }
+
+    /// Sets if the border under the mouse is highlighted to show that it can
+    /// be dragged
+    pub fn set_hover_highlight(&mut self, hover_highlight: bool) {
+        self.hover_highlight = hover_highlight;
+        self.invalidate();
+    }
+
+    pub fn hover_highlight(mut self, hover_highlight: bool) -> Self {
+        self.set_hover_highlight(hover_highlight);
+        self
+    }
+
+    pub fn get_scroll_policy(&self) -> ScrollPolicy {
+        self.scroll_policy.clone()
+    }
+
+    /// Sets when this component receives scroll wheel events
+    pub fn set_scroll_policy(&mut self, scroll_policy: ScrollPolicy) {
+        self.scroll_policy = scroll_policy;
+    }
+
+    pub fn scroll_policy(mut self, scroll_policy: ScrollPolicy) -> Self {
+        self.set_scroll_policy(scroll_policy);
+        self
+    }
+
+    pub fn get_focus_policy(&self) -> FocusPolicy {
+        self.focus_policy.clone()
+    }
+
+    /// Sets how the mouse gives focus to this component
+    pub fn set_focus_policy(&mut self, focus_policy: FocusPolicy) {
+        self.focus_policy = focus_policy;
+    }
+
+    pub fn focus_policy(mut self, focus_policy: FocusPolicy) -> Self {
+        self.set_focus_policy(focus_policy);
+        self
+    }
+
+    /// Returns the context menu items of the widget for a right click at the
+    /// position in the component
+    pub fn get_menu_items(&self, x: u16, y: u16) -> Vec<MenuItem> {
+        let (in_component, in_widget) = self.contains(x, y);
+        if !in_component {
+            return Vec::new();
+        }
+        let position = in_widget.then(|| (x - self.border_width, y - self.border_width));
+        self.widget.get_menu_items(position)
+    }
+
+    /// Sends the action of a chosen context menu item to the widget
+    pub fn handle_menu(&mut self, action: &str) -> EventResult {
+        let result = self.widget.handle_menu(action);
+        if matches!(result, EventResult::Redraw | EventResult::Action(_)) {
+            self.invalidate();
+        }
+        result
+    }
+
+    /// Returns if the position is inside the component and inside the widget
+    fn contains(&self, x: u16, y: u16) -> (bool, bool) {
+        let in_component = x < self.width && y < self.height;
+        let in_widget = x >= self.border_width
+            && y >= self.border_width
+            && x < self.width - self.border_width
+            && y < self.height - self.border_width;
+        (in_component, in_widget)
+    }
+
+    /// Updates the hover state, notifying the widget if the mouse entered or
+    /// left the component
+    fn set_hover(&mut self, hovered: bool, hover_border: Option<Border>) {
+        if self.hover_border != hover_border {
+            self.hover_border = hover_border;
+            self.invalidate();
+        }
+        if self.hovered != hovered {
+            self.hovered = hovered;
+            if self.widget.handle_hover(hovered) {
+                self.invalidate();
+            }
+        }
+    }
+
     pub fn get_widget(&self) -> &dyn ComponentWidget {
         &*self.widget
     }
@@ -145,6 +394,111 @@ impl Component {
         self.set_fixed_height(fixed_height);
         self
     }
+
+    pub fn is_adjustable(&self) -> bool {
+        self.adjustable
+    }
+
+    /// Sets if the user can resize this component when it has a fixed size,
+    /// which changes the fixed size to what they resized it to
+    pub fn set_adjustable(&mut self, adjustable: bool) {
+        self.adjustable = adjustable;
+    }
+
+    pub fn adjustable(mut self, adjustable: bool) -> Self {
+        self.set_adjustable(adjustable);
+        self
+    }
+
+    pub fn get_min_width(&self) -> Option<u16> {
+        self.min_width
+    }
+
+    /// Sets the smallest width the user can resize this component to
+    pub fn set_min_width(&mut self, min_width: Option<u16>) {
+        self.min_width = min_width;
+    }
+
+    pub fn min_width(mut self, min_width: Option<u16>) -> Self {
+        self.set_min_width(min_width);
+        self
+    }
+
+    pub fn get_min_height(&self) -> Option<u16> {
+        self.min_height
+    }
+
+    /// Sets the smallest height the user can resize this component to
+    pub fn set_min_height(&mut self, min_height: Option<u16>) {
+        self.min_height = min_height;
+    }
+
+    pub fn min_height(mut self, min_height: Option<u16>) -> Self {
+        self.set_min_height(min_height);
+        self
+    }
+
+    pub fn get_max_width(&self) -> Option<u16> {
+        self.max_width
+    }
+
+    /// Sets the largest width the user can resize this component to
+    pub fn set_max_width(&mut self, max_width: Option<u16>) {
+        self.max_width = max_width;
+    }
+
+    pub fn max_width(mut self, max_width: Option<u16>) -> Self {
+        self.set_max_width(max_width);
+        self
+    }
+
+    pub fn get_max_height(&self) -> Option<u16> {
+        self.max_height
+    }
+
+    /// Sets the largest height the user can resize this component to
+    pub fn set_max_height(&mut self, max_height: Option<u16>) {
+        self.max_height = max_height;
+    }
+
+    pub fn max_height(mut self, max_height: Option<u16>) -> Self {
+        self.set_max_height(max_height);
+        self
+    }
+
+    /// Sets if this component can take focus, unfocusing it if not
+    pub fn set_focusable(&mut self, focusable: bool) {
+        self.focusable = focusable;
+        if !focusable {
+            self.set_focus(Focus::None);
+        }
+    }
+
+    pub fn focusable(mut self, focusable: bool) -> Self {
+        self.set_focusable(focusable);
+        self
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// Sets if this component is disabled, a disabled component is rendered
+    /// dimmed, cannot take focus, and ignores all input
+    pub fn set_disabled(&mut self, disabled: bool) {
+        if self.disabled != disabled {
+            self.disabled = disabled;
+            self.invalidate();
+        }
+        if disabled {
+            self.set_focus(Focus::None);
+        }
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.set_disabled(disabled);
+        self
+    }
 }
 
 impl ComponentBase for Component {
@@ -152,22 +506,62 @@ impl ComponentBase for Component {
         // Check if the mouse event is none, unfocus if true
         let Some(kind) = kind else {
             self.set_focus(Focus::None);
+            self.handle_mouse_leave();
+            self.captured = false;
             return;
         };
-        // Ignore mouse move events (TODO: Consider reworking this)
-        if let MouseEventKind::Moved = kind {
+        // Disabled components ignore all input
+        if self.disabled {
+            return;
+        }
+        // Scroll events go to handle_scroll instead, which has the modifiers
+        if let MouseEventKind::ScrollDown | MouseEventKind::ScrollUp = kind {
             return;
         }
         // Determine if the mouse event is in the component and the widget
-        let in_component = x < self.width && y < self.height;
-        let in_widget = x >= self.border_width
-            && y >= self.border_width
-            && x < self.width - self.border_width
-            && y < self.height - self.border_width;
+        let (in_component, in_widget) = self.contains(x, y);
+        if in_component {
+            self.set_hover(true, self.get_border(x, y));
+        } else {
+            self.handle_mouse_leave();
+        }
+        // Send mouse move events to the widget under the mouse regardless of focus
+        if let MouseEventKind::Moved = kind {
+            let follow = self.focus_policy == FocusPolicy::FollowMouse;
+            if follow && in_component && self.focus == Focus::None && self.is_focusable() {
+                self.set_focus(Focus::Focus);
+            }
+            if in_widget {
+                let (x, y) = (x - self.border_width, y - self.border_width);
+                if self.widget.handle_mouse(x, y, kind) {
+                    self.invalidate();
+                }
+            }
+            return;
+        }
+        // Send drags and the release to the widget the drag started in
+        if let (true, MouseEventKind::Drag(_) | MouseEventKind::Up(_)) = (self.captured, kind) {
+            self.handle_mouse_captured(x as i32, y as i32, kind);
+            return;
+        }
         // Check if the mouse event should focus this component
+        let focusable = self.is_focusable();
+        if let MouseEventKind::Down(_) = kind {
+            self.focus_click = false;
+        }
         match kind {
-            MouseEventKind::Down(MouseButton::Left) if in_component => self.set_focus(Focus::Focus),
-            MouseEventKind::Drag(MouseButton::Left) if in_component => self.set_focus(Focus::Focus),
+            MouseEventKind::Down(MouseButton::Left) if in_component && focusable => {
+                // The first click can be used only to focus the component
+                self.focus_click =
+                    self.focus_policy == FocusPolicy::ClickToFocus && self.focus != Focus::Focus;
+                self.set_focus(Focus::Focus);
+                if self.focus_click {
+                    return;
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) if in_component && focusable => {
+                self.set_focus(Focus::Focus)
+            }
             _ => {}
         }
         // Send mouse event to widget if mouse is in widget and component focused
@@ -176,43 +570,136 @@ impl ComponentBase for Component {
             if self.widget.handle_mouse(x, y, kind) {
                 self.invalidate();
             }
+            if let MouseEventKind::Down(_) = kind {
+                self.captured = true;
+            }
         }
     }
 
-    fn handle_key(&mut self, e: KeyEvent) -> Option<Border> {
+    fn handle_mouse_leave(&mut self) {
+        self.set_hover(false, None);
+    }
+
+    fn handle_mouse_captured(&mut self, x: i32, y: i32, kind: MouseEventKind) {
+        if !self.captured || self.disabled {
+            return;
+        }
+        if let MouseEventKind::Up(_) = kind {
+            self.captured = false;
+        }
+        // Use the normal handler while the mouse is back inside the widget
+        let border_width = self.border_width as i32;
+        let (x, y) = (x - border_width, y - border_width);
+        let width = self.width as i32 - border_width * 2;
+        let height = self.height as i32 - border_width * 2;
+        let redraw = if x >= 0 && y >= 0 && x < width && y < height {
+            self.widget.handle_mouse(x as u16, y as u16, kind)
+        } else {
+            self.widget.handle_mouse_captured(x, y, kind)
+        };
+        if redraw {
+            self.invalidate();
+        }
+    }
+
+    fn handle_click(&mut self, x: u16, y: u16, button: MouseButton, count: u8) {
+        // Clicks go to the widget the same as the mouse down before them
+        let (_, in_widget) = self.contains(x, y);
+        if self.disabled || !in_widget || self.focus != Focus::Focus || self.focus_click {
+            return;
+        }
+        let (x, y) = (x - self.border_width, y - self.border_width);
+        if self.widget.handle_click(x, y, button, count) {
+            self.invalidate();
+        }
+    }
+
+    fn handle_scroll(&mut self, x: u16, y: u16, kind: MouseEventKind, modifiers: KeyModifiers) {
+        // Disabled components ignore all input
+        if self.disabled {
+            return;
+        }
+        let (in_component, in_widget) = self.contains(x, y);
+        if !in_component {
+            return;
+        }
+        let deliver = match self.scroll_policy {
+            ScrollPolicy::Focused => self.focus == Focus::Focus,
+            ScrollPolicy::Hovered => true,
+            ScrollPolicy::FocusHovered(without_focus) => {
+                let focus = without_focus.is_empty() || !modifiers.contains(without_focus);
+                if focus && self.is_focusable() {
+                    self.set_focus(Focus::Focus);
+                }
+                true
+            }
+        };
+        if in_widget && deliver {
+            let (x, y) = (x - self.border_width, y - self.border_width);
+            if self.widget.handle_mouse(x, y, kind) {
+                self.invalidate();
+            }
+        }
+    }
+
+    fn handle_key(&mut self, e: KeyEvent) -> EventResult {
+        // Disabled components ignore all input
+        if self.disabled {
+            return EventResult::Ignored;
+        }
         match self.get_focus() {
             Focus::Focus => match e.code {
-                KeyCode::Esc => self.set_focus(Focus::PartialFocus),
+                KeyCode::Esc => {
+                    self.set_focus(Focus::PartialFocus);
+                    EventResult::Redraw
+                }
                 _ => {
-                    if self.widget.handle_key(e) {
+                    let result = self.widget.handle_key(e);
+                    if matches!(result, EventResult::Redraw | EventResult::Action(_)) {
                         self.invalidate();
                     }
+                    result
                 }
             },
             Focus::PartialFocus => match e.code {
-                KeyCode::Up => {
-                    self.set_focus(Focus::None);
-                    return Some(Border::Top);
+                KeyCode::Up => EventResult::Border(Border::Top),
+                KeyCode::Down => EventResult::Border(Border::Bottom),
+                KeyCode::Left => EventResult::Border(Border::Left),
+                KeyCode::Right => EventResult::Border(Border::Right),
+                KeyCode::Enter => {
+                    self.set_focus(Focus::Focus);
+                    EventResult::Redraw
                 }
-                KeyCode::Down => {
-                    self.set_focus(Focus::None);
-                    return Some(Border::Bottom);
-                }
-                KeyCode::Left => {
-                    self.set_focus(Focus::None);
-                    return Some(Border::Left);
-                }
-                KeyCode::Right => {
-                    self.set_focus(Focus::None);
-                    return Some(Border::Right);
-                }
-                KeyCode::Enter => self.set_focus(Focus::Focus),
-                _ => {}
+                _ => EventResult::Ignored,
             },
-            Focus::None if e.code == KeyCode::Enter => self.set_focus(Focus::Focus),
-            _ => {}
+            Focus::None if e.code == KeyCode::Enter && self.is_focusable() => {
+                self.set_focus(Focus::Focus);
+                EventResult::Redraw
+            }
+            Focus::None => EventResult::Ignored,
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) -> EventResult {
+        // Only a fully focused widget receives pasted text
+        if self.disabled || self.focus != Focus::Focus {
+            return EventResult::Ignored;
+        }
+        let result = self.widget.handle_paste(text);
+        if matches!(result, EventResult::Redraw | EventResult::Action(_)) {
+            self.invalidate();
+        }
+        result
+    }
+
+    fn handle_terminal_focus(&mut self, focused: bool) {
+        if self.terminal_focused != focused {
+            self.terminal_focused = focused;
+            self.invalidate();
+        }
+        if self.widget.handle_terminal_focus(focused) {
+            self.invalidate();
         }
-        None
     }
 
     fn handle_update(&mut self) {
@@ -264,6 +751,29 @@ impl ComponentBase for Component {
         self.fixed_height
     }
 
+    fn get_size_range(&self, direction: Direction) -> (u16, u16) {
+        let (fixed, size, min, max) = match direction {
+            Direction::Horizontal => (self.fixed_width, self.width, self.min_width, self.max_width),
+            Direction::Vertical => (
+                self.fixed_height,
+                self.height,
+                self.min_height,
+                self.max_height,
+            ),
+        };
+        if fixed && !self.adjustable {
+            return (size, size);
+        }
+        // Always leave room for the borders
+        let border_min = std::cmp::max(self.border_width * 2, 1);
+        let min = std::cmp::max(min.unwrap_or_default(), border_min);
+        (min, std::cmp::max(max.unwrap_or(u16::MAX), min))
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.focusable && !self.disabled
+    }
+
     fn render(&mut self, area: Rect, buf: &mut Buffer) {
         if !self.invalidated {
             return;
@@ -272,19 +782,53 @@ impl ComponentBase for Component {
         // Render borders if they are present
         if self.border_width > 0 {
             let border_color = match self.focus {
+                _ if self.disabled => Color::DarkGray,
                 Focus::Focus => Color::Green,
                 Focus::PartialFocus => Color::Yellow,
                 Focus::None => Color::White,
             };
+            // Dim the border while the terminal is unfocused
+            let border_style = if self.terminal_focused {
+                Style::default().fg(border_color)
+            } else {
+                Style::default()
+                    .fg(border_color)
+                    .add_modifier(Modifier::DIM)
+            };
             let block = Block::default()
                 .borders(Borders::ALL)
-                .style(Style::default().fg(border_color))
+                .style(border_style)
                 .border_type(BorderType::Rounded);
             if !self.get_name().is_empty() {
                 block.title(self.get_name()).render(area, buf)
             } else {
                 block.render(area, buf)
             }
+            // Highlight the border under the mouse
+            if let Some(border) = self.hover_border.as_ref().filter(|_| self.hover_highlight) {
+                let width = std::cmp::min(self.border_width, area.width);
+                let height = std::cmp::min(self.border_width, area.height);
+                let rect = match border {
+                    Border::Top => Rect { height, ..area },
+                    Border::Bottom => Rect {
+                        y: area.y + area.height - height,
+                        height,
+                        ..area
+                    },
+                    Border::Left => Rect { width, ..area },
+                    Border::Right => Rect {
+                        x: area.x + area.width - width,
+                        width,
+                        ..area
+                    },
+                };
+                buf.set_style(
+                    rect,
+                    Style::default()
+                        .fg(Color::LightCyan)
+                        .add_modifier(Modifier::BOLD),
+                );
+            }
         }
         // Do not render widget if borders take whole area
         if area.width <= self.get_border_width() * 2 || area.height <= self.get_border_width() * 2 {
@@ -305,6 +849,10 @@ impl ComponentBase for Component {
         }
         // Render widget
         self.widget.render(rect, buf);
+        // Dim widget if disabled
+        if self.disabled {
+            buf.set_style(rect, Style::default().add_modifier(Modifier::DIM));
+        }
     }
 
     fn get_focus(&self) -> Focus {
@@ -331,6 +879,20 @@ impl ComponentBase for Component {
             None
         }
     }
+
+    fn get_border_along(&self, x: u16, y: u16, direction: Direction) -> Option<Border> {
+        if x >= self.get_width() || y >= self.get_height() {
+            return None;
+        }
+        let border_width = self.get_border_width();
+        match direction {
+            Direction::Horizontal if x < border_width => Some(Border::Left),
+            Direction::Horizontal if x >= self.get_width() - border_width => Some(Border::Right),
+            Direction::Vertical if y < border_width => Some(Border::Top),
+            Direction::Vertical if y >= self.get_height() - border_width => Some(Border::Bottom),
+            _ => None,
+        }
+    }
 }
 
 pub struct ComponentBaseWidget<'a> {
@@ -6,7 +6,7 @@ use tui::{
     widgets::{Paragraph, Widget},
 };
 
-use crate::component::ComponentWidget;
+use crate::{component::ComponentWidget, EventResult};
 
 pub struct ComponentWidgetSimple {
     text: String,
@@ -74,8 +74,8 @@ impl ComponentWidget for ComponentWidgetSimple {
         true
     }
 
-    fn handle_key(&mut self, _e: KeyEvent) -> bool {
-        true
+    fn handle_key(&mut self, _e: KeyEvent) -> EventResult {
+        EventResult::Ignored
     }
 
     fn handle_update(&mut self) -> bool {
@@ -1,9 +1,16 @@
+pub mod accordion;
+pub mod auto;
 pub mod list;
+pub mod scroll;
 pub mod search;
+pub mod virtual_list;
 
 use tui::layout::Rect;
 
-use crate::component::{Component, ComponentBase};
+use crate::{
+    component::{Component, ComponentBase},
+    Border, MenuItem, ResizeError,
+};
 
 pub enum ContainerChild {
     Container(Box<dyn Container>),
@@ -69,11 +76,83 @@ where
     fn get_children_mut(&mut self) -> &mut Vec<ContainerChild>;
     fn get_children_rectangles(&self) -> Vec<Rect>;
 
+    /// Returns the part of the child at the index shown in its rectangle, in
+    /// the coordinates of the child, for containers showing only part of a
+    /// child larger than its rectangle
+    fn get_child_view(&self, _index: usize) -> Option<Rect> {
+        None
+    }
+
     fn as_base(&self) -> &dyn ComponentBase;
     fn as_base_mut(&mut self) -> &mut dyn ComponentBase;
 
     fn is_resizable(&self) -> bool;
 
+    /// Removes the child with the matching name from the container, resizing
+    /// the remaining children to fill the space, and returns none if there
+    /// was no matching child or the remaining children could not be resized
+    fn remove_child(&mut self, _name: &str) -> Option<ContainerChild> {
+        None
+    }
+
+    /// Grows the child with the matching name by moving its border outwards
+    /// by the delta (or inwards if negative), shrinking the neighbouring child
+    /// across that border, and returns false if there is no such neighbour or
+    /// their sizes do not allow it
+    fn resize_child(&mut self, _name: &str, _border: Border, _delta: i16) -> bool {
+        false
+    }
+
+    /// Sets all the children without a fixed size to the same size, including
+    /// the children of nested containers if recursive, and rolls back the
+    /// sizes of this container if any child could not be resized
+    fn equalize(&mut self, _recursive: bool) -> Result<(), ResizeError> {
+        Ok(())
+    }
+
+    /// Moves each child to the next position, or the previous if reversed,
+    /// taking the size of that position, and fails for containers without
+    /// positions to move between
+    fn rotate(&mut self, _reverse: bool) -> Result<(), ResizeError> {
+        Err(unsupported(self))
+    }
+
+    /// Switches the orientation of the container, keeping the proportions of
+    /// the children, and fails for containers without an orientation
+    fn flip(&mut self) -> Result<(), ResizeError> {
+        Err(unsupported(self))
+    }
+
+    /// Switches to the next way of arranging the children, such as the next
+    /// layout algorithm or the other orientation, and fails for containers
+    /// with only one arrangement
+    fn next_layout(&mut self) -> Result<(), ResizeError> {
+        Err(unsupported(self))
+    }
+
+    /// Lays out the children again for the current size of the container,
+    /// for when a child was resized from outside of the container
+    fn refresh(&mut self) -> Result<(), ResizeError> {
+        Ok(())
+    }
+
+    /// Returns the context menu items added for right clicks on any child,
+    /// chosen items are returned to the application as actions
+    fn get_menu_items(&self) -> Vec<MenuItem> {
+        Vec::new()
+    }
+
     fn as_container(&self) -> &dyn Container;
     fn as_container_mut(&mut self) -> &mut dyn Container;
 }
+
+/// The error for a layout command the container does not support, leaving the
+/// container at its current size
+fn unsupported<T: ComponentBase + ?Sized>(container: &T) -> ResizeError {
+    ResizeError {
+        name: container.get_name(),
+        width: container.get_width(),
+        height: container.get_height(),
+        border_width: 0,
+    }
+}
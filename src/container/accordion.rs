@@ -0,0 +1,704 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+use tui::{
+    buffer::Buffer,
+    layout::{Direction, Rect},
+    style::{Color, Modifier, Style},
+};
+
+use crate::{
+    container::auto::split_even, container::list::*, container::search::ContainerSearch,
+    container::*, Border, EventResult, Focus, FocusResult, MenuItem, ResizeError,
+};
+
+/// Vertical container of sections with a title row each, where only a number
+/// of sections are expanded to show their child and the rest are collapsed
+/// to their title
+pub struct ContainerAccordion {
+    name: String,
+    max_expanded: usize,
+    expanded: Vec<usize>,
+    title_hover: Option<usize>,
+    capture: Option<usize>,
+    width: u16,
+    height: u16,
+    children: Vec<ContainerChild>,
+    menu_items: Vec<MenuItem>,
+}
+
+impl ContainerAccordion {
+    pub fn new(name: String, width: u16, height: u16) -> Self {
+        Self {
+            name,
+            max_expanded: 1,
+            expanded: Vec::new(),
+            title_hover: None,
+            capture: None,
+            width,
+            height,
+            children: Vec::new(),
+            menu_items: Vec::new(),
+        }
+    }
+
+    /// Adds a new collapsed section to the container, and returns an error
+    /// without adding it if there was no room available for its title
+    pub fn add_component(&mut self, child: Component) -> Result<(), ResizeError> {
+        self.add_child(ContainerChild::Component(child))
+    }
+
+    /// Adds a new collapsed section to the container, and returns an error
+    /// without adding it if there was no room available for its title
+    pub fn add_container(&mut self, child: Box<dyn Container>) -> Result<(), ResizeError> {
+        self.add_child(ContainerChild::Container(child))
+    }
+
+    fn add_child(&mut self, child: ContainerChild) -> Result<(), ResizeError> {
+        let expanded = self.expanded.clone();
+        self.children.push(child);
+        if let Err(err) = self.refresh() {
+            self.children.pop();
+            self.expanded = expanded;
+            let _ = self.refresh();
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Sets the sections of the container, with only the first one expanded
+    pub fn from_children(mut self, children: Vec<ContainerChild>) -> Result<Self, ResizeError> {
+        for child in children {
+            self.children.push(child);
+        }
+        self.refresh()?;
+        Ok(self)
+    }
+
+    /// Sets the context menu items shown for right clicks on any child
+    pub fn set_menu_items(&mut self, menu_items: Vec<MenuItem>) {
+        self.menu_items = menu_items;
+    }
+
+    pub fn menu_items(mut self, menu_items: Vec<MenuItem>) -> Self {
+        self.set_menu_items(menu_items);
+        self
+    }
+
+    pub fn get_max_expanded(&self) -> usize {
+        self.max_expanded
+    }
+
+    /// Sets how many sections can be expanded at the same time, collapsing
+    /// the sections expanded longest ago if there are too many
+    pub fn set_max_expanded(&mut self, max_expanded: usize) -> Result<(), ResizeError> {
+        let previous = (self.max_expanded, self.expanded.clone());
+        self.max_expanded = std::cmp::max(max_expanded, 1);
+        let excess = self.expanded.len().saturating_sub(self.max_expanded);
+        self.expanded.drain(..excess);
+        if let Err(err) = self.refresh() {
+            (self.max_expanded, self.expanded) = previous;
+            let _ = self.refresh();
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    pub fn max_expanded(mut self, max_expanded: usize) -> Result<Self, ResizeError> {
+        self.set_max_expanded(max_expanded)?;
+        Ok(self)
+    }
+
+    /// Returns the names of the expanded sections, from the one expanded
+    /// longest ago
+    pub fn get_expanded(&self) -> Vec<String> {
+        self.expanded
+            .iter()
+            .map(|&i| self.children[i].as_base().get_name())
+            .collect()
+    }
+
+    pub fn is_expanded(&self, name: &str) -> bool {
+        self.get_index(name)
+            .is_some_and(|index| self.expanded.contains(&index))
+    }
+
+    /// Expands the section with the matching name, collapsing the section
+    /// expanded longest ago if too many are expanded, and returns false if
+    /// there is no such section or it was already expanded
+    pub fn expand(&mut self, name: &str) -> Result<bool, ResizeError> {
+        match self.get_index(name) {
+            Some(index) => self.expand_index(index),
+            None => Ok(false),
+        }
+    }
+
+    /// Collapses the section with the matching name, and returns false if
+    /// there is no such section, it was not expanded or it is the only
+    /// section expanded
+    pub fn collapse(&mut self, name: &str) -> Result<bool, ResizeError> {
+        let Some(index) = self.get_index(name) else {
+            return Ok(false);
+        };
+        if self.expanded.len() < 2 || !self.expanded.contains(&index) {
+            return Ok(false);
+        }
+        let previous = self.expanded.clone();
+        self.expanded.retain(|&i| i != index);
+        if let Err(err) = self.refresh() {
+            self.expanded = previous;
+            let _ = self.refresh();
+            return Err(err);
+        }
+        Ok(true)
+    }
+
+    fn expand_index(&mut self, index: usize) -> Result<bool, ResizeError> {
+        if self.expanded.contains(&index) {
+            return Ok(false);
+        }
+        let previous = self.expanded.clone();
+        self.expanded.push(index);
+        let excess = self.expanded.len().saturating_sub(self.max_expanded);
+        self.expanded.drain(..excess);
+        if let Err(err) = self.refresh() {
+            self.expanded = previous;
+            let _ = self.refresh();
+            return Err(err);
+        }
+        Ok(true)
+    }
+
+    /// Returns the index of the section with the matching name
+    fn get_index(&self, name: &str) -> Option<usize> {
+        self.children
+            .iter()
+            .position(|c| c.as_base().get_name() == name)
+    }
+
+    /// Calculates the row of the title of each section and where its child
+    /// goes, sharing the rows left by the titles between the expanded
+    /// sections and leaving the collapsed children without any rows
+    fn get_layout(&self, width: u16, height: u16) -> Vec<(u16, Rect)> {
+        let count = self.children.len();
+        let mut heights = vec![0; count];
+        if !self.expanded.is_empty() {
+            let mut expanded = self.expanded.clone();
+            expanded.sort();
+            let rows = height.saturating_sub(count as u16);
+            for (i, rows) in expanded
+                .into_iter()
+                .zip(split_even(rows, self.expanded.len()))
+            {
+                heights[i] = rows;
+            }
+        }
+        let mut y = 0;
+        heights
+            .into_iter()
+            .map(|height| {
+                let title = y;
+                y += height + 1;
+                let rect = Rect {
+                    x: 0,
+                    y: title + 1,
+                    width,
+                    height,
+                };
+                (title, rect)
+            })
+            .collect()
+    }
+
+    /// Resizes the expanded children to their place for the new size, rolling
+    /// back to the previous sizes if any child fails to resize
+    fn resize_children(&mut self, width: u16, height: u16) -> Result<(), ResizeError> {
+        let count = self.children.len() as u16;
+        if count > height {
+            return Err(ResizeError {
+                name: self.name.clone(),
+                width,
+                height,
+                border_width: 0,
+            });
+        }
+        let layout = self.get_layout(width, height);
+        let old_dimensions = self
+            .children
+            .iter()
+            .map(|c| (c.as_base().get_width(), c.as_base().get_height()))
+            .collect::<Vec<(u16, u16)>>();
+        for &i in &self.expanded {
+            let rect = layout[i].1;
+            let result = self.children[i]
+                .as_base_mut()
+                .resize(rect.width, rect.height);
+            if let Err(err) = result {
+                for (i, dim) in old_dimensions.iter().enumerate() {
+                    let _ = self.children[i].as_base_mut().resize(dim.0, dim.1);
+                }
+                return Err(err);
+            }
+        }
+        self.width = width;
+        self.height = height;
+        self.capture = None;
+        self.invalidate();
+        Ok(())
+    }
+
+    /// Gives partial focus to the first component in the section that can
+    /// take focus, returning false if there is none
+    fn focus_section(&mut self, index: usize) -> bool {
+        match &mut self.children[index] {
+            ContainerChild::Component(component) if component.is_focusable() => {
+                component.set_focus(Focus::PartialFocus);
+                true
+            }
+            ContainerChild::Component(_) => false,
+            ContainerChild::Container(container) => {
+                let Some((_, pos)) = container.as_container().search_focusable() else {
+                    return false;
+                };
+                if let Some((component, _)) = container.as_container_mut().search_position_mut(pos)
+                {
+                    component.set_focus(Focus::PartialFocus);
+                }
+                true
+            }
+        }
+    }
+
+    /// Moves focus from the section with focus to the next section above or
+    /// below that can take focus, returning the border for any other move or
+    /// if there is no such section
+    fn handle_focus_move(&mut self, index: usize, border: Border) -> EventResult {
+        let next = match border {
+            Border::Top => (0..index)
+                .rev()
+                .find(|&i| self.children[i].as_base().is_focusable()),
+            Border::Bottom => (index + 1..self.children.len())
+                .find(|&i| self.children[i].as_base().is_focusable()),
+            _ => None,
+        };
+        let Some(next) = next else {
+            return EventResult::Border(border);
+        };
+        clear_focus(self);
+        self.focus_section(next);
+        EventResult::Redraw
+    }
+
+    /// Returns the index of the section with its title at the position
+    fn get_title_index(&self, x: u16, y: u16) -> Option<usize> {
+        if x >= self.width {
+            return None;
+        }
+        self.get_layout(self.width, self.height)
+            .iter()
+            .position(|(title, _)| *title == y)
+    }
+
+    /// Returns the border of the child if it is also on the outside of this
+    /// container
+    fn get_outer_border(&self, rect: Rect, border: Border) -> Option<Border> {
+        let outer = match border {
+            Border::Left => rect.x == 0,
+            Border::Top => rect.y == 0,
+            Border::Right => rect.x + rect.width >= self.width,
+            Border::Bottom => rect.y + rect.height >= self.height,
+        };
+        outer.then_some(border)
+    }
+
+    /// Draws the title row of each section, coloured like the border of a
+    /// component with the same focus
+    fn render_titles(&self, area: Rect, buf: &mut Buffer) {
+        let layout = self.get_layout(area.width, area.height);
+        for (i, (title, _)) in layout.into_iter().enumerate() {
+            let child = self.children[i].as_base();
+            let style = if self.title_hover == Some(i) {
+                Style::default()
+                    .fg(Color::LightCyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                match child.get_focus() {
+                    Focus::Focus => Style::default().fg(Color::Green),
+                    Focus::PartialFocus => Style::default().fg(Color::Yellow),
+                    Focus::None => Style::default().fg(Color::White),
+                }
+            };
+            let symbol = match self.expanded.contains(&i) {
+                true => '▼',
+                false => '▶',
+            };
+            let row = Rect {
+                y: area.y + title,
+                height: 1,
+                ..area
+            };
+            buf.set_style(row, style);
+            for x in row.left()..row.right() {
+                buf.get_mut(x, row.y).set_char('─');
+            }
+            let text = format!("{} {} ", symbol, child.get_name());
+            buf.set_stringn(row.x, row.y, text, row.width as usize, style);
+        }
+    }
+}
+
+impl Container for ContainerAccordion {
+    fn get_children(&self) -> &Vec<ContainerChild> {
+        &self.children
+    }
+
+    fn get_children_mut(&mut self) -> &mut Vec<ContainerChild> {
+        &mut self.children
+    }
+
+    /// Collapsed children are placed under their title without any rows
+    fn get_children_rectangles(&self) -> Vec<Rect> {
+        self.get_layout(self.width, self.height)
+            .into_iter()
+            .map(|(_, rect)| rect)
+            .collect()
+    }
+
+    fn as_base(&self) -> &dyn ComponentBase {
+        self
+    }
+
+    fn as_base_mut(&mut self) -> &mut dyn ComponentBase {
+        self
+    }
+
+    fn is_resizable(&self) -> bool {
+        false
+    }
+
+    fn refresh(&mut self) -> Result<(), ResizeError> {
+        self.expanded.retain(|&i| i < self.children.len());
+        if self.expanded.is_empty() && !self.children.is_empty() {
+            self.expanded.push(0);
+        }
+        // Hidden components keep partial focus so their title shows it, but
+        // must not take keys meant for a visible component
+        for (i, child) in self.children.iter_mut().enumerate() {
+            if self.expanded.contains(&i) {
+                continue;
+            }
+            match child {
+                ContainerChild::Component(component) if component.get_focus() == Focus::Focus => {
+                    component.set_focus(Focus::PartialFocus);
+                }
+                ContainerChild::Component(_) => {}
+                ContainerChild::Container(container) => {
+                    if let FocusResult::Focus((component, _)) =
+                        container.as_container_mut().search_focused_mut()
+                    {
+                        component.set_focus(Focus::PartialFocus);
+                    }
+                }
+            }
+        }
+        // Nothing to lay out until the container has been sized
+        if self.width == 0 && self.height == 0 {
+            return Ok(());
+        }
+        self.resize_children(self.width, self.height)
+    }
+
+    fn get_menu_items(&self) -> Vec<MenuItem> {
+        self.menu_items.clone()
+    }
+
+    fn equalize(&mut self, recursive: bool) -> Result<(), ResizeError> {
+        if !recursive {
+            return Ok(());
+        }
+        for child in &mut self.children {
+            if let ContainerChild::Container(container) = child {
+                container.equalize(true)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves each section to the next position, or the previous if reversed,
+    /// keeping the same sections expanded
+    fn rotate(&mut self, reverse: bool) -> Result<(), ResizeError> {
+        let count = self.children.len();
+        if count < 2 {
+            return Ok(());
+        }
+        let previous = self.expanded.clone();
+        match reverse {
+            true => self.children.rotate_left(1),
+            false => self.children.rotate_right(1),
+        }
+        for i in &mut self.expanded {
+            *i = match reverse {
+                true => (*i + count - 1) % count,
+                false => (*i + 1) % count,
+            };
+        }
+        if let Err(err) = self.refresh() {
+            match reverse {
+                true => self.children.rotate_right(1),
+                false => self.children.rotate_left(1),
+            }
+            self.expanded = previous;
+            let _ = self.refresh();
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Removes the section, expanding the section after it instead if it was
+    /// the only one expanded
+    fn remove_child(&mut self, name: &str) -> Option<ContainerChild> {
+        let index = self.get_index(name)?;
+        let previous = self.expanded.clone();
+        let child = self.children.remove(index);
+        let was_only = self.expanded == [index];
+        self.expanded.retain(|&i| i != index);
+        for i in &mut self.expanded {
+            if *i > index {
+                *i -= 1;
+            }
+        }
+        if was_only && !self.children.is_empty() {
+            self.expanded.push(index.min(self.children.len() - 1));
+        }
+        if self.refresh().is_err() {
+            self.children.insert(index, child);
+            self.expanded = previous;
+            let _ = self.refresh();
+            return None;
+        }
+        Some(child)
+    }
+
+    fn as_container(&self) -> &dyn Container {
+        self
+    }
+
+    fn as_container_mut(&mut self) -> &mut dyn Container {
+        self
+    }
+}
+
+impl ComponentBase for ContainerAccordion {
+    fn handle_mouse(&mut self, x: u16, y: u16, kind: Option<MouseEventKind>) {
+        // Check if the mouse event is none
+        let Some(kind) = kind else {
+            // Issue none to all children
+            for_each_child(&mut self.children, |c| c.handle_mouse(0, 0, None));
+            self.capture = None;
+            self.title_hover = None;
+            return;
+        };
+        // Send mouse move events to the child under the mouse, letting the rest
+        // know the mouse left them without changing focus
+        if let MouseEventKind::Moved = kind {
+            self.title_hover = self.get_title_index(x, y);
+            hover_child(self, x, y);
+            return;
+        }
+        // Scroll events go to handle_scroll instead, which has the modifiers
+        if let MouseEventKind::ScrollDown | MouseEventKind::ScrollUp = kind {
+            return;
+        }
+        // Send drags and the release to the child the drag started in
+        if let (Some(index), MouseEventKind::Drag(_) | MouseEventKind::Up(_)) = (self.capture, kind)
+        {
+            if let MouseEventKind::Up(_) = kind {
+                self.capture = None;
+            }
+            handle_captured_child(self, index, x as i32, y as i32, kind);
+            return;
+        }
+        // Clicking a title expands its section, or collapses it if other
+        // sections are expanded too
+        if let MouseEventKind::Down(MouseButton::Left) = kind {
+            if let Some(index) = self.get_title_index(x, y) {
+                let name = self.children[index].as_base().get_name();
+                let _ = match self.expanded.contains(&index) {
+                    true => self.collapse(&name),
+                    false => self.expand_index(index),
+                };
+                return;
+            }
+        }
+        let index = get_child_index(self, x, y);
+        if let MouseEventKind::Down(_) = kind {
+            self.capture = index;
+        }
+        // Dispatch the mouse event to the child under the mouse, issuing none
+        // to the rest
+        dispatch_child(self, index, x, y, kind);
+    }
+
+    fn handle_mouse_leave(&mut self) {
+        self.title_hover = None;
+        for_each_child(&mut self.children, |c| c.handle_mouse_leave());
+    }
+
+    fn handle_mouse_captured(&mut self, x: i32, y: i32, kind: MouseEventKind) {
+        let Some(index) = self.capture else {
+            return;
+        };
+        if let MouseEventKind::Up(_) = kind {
+            self.capture = None;
+        }
+        handle_captured_child(self, index, x, y, kind);
+    }
+
+    fn handle_click(&mut self, x: u16, y: u16, button: MouseButton, count: u8) {
+        click_child(self, x, y, button, count);
+    }
+
+    fn handle_scroll(&mut self, x: u16, y: u16, kind: MouseEventKind, modifiers: KeyModifiers) {
+        scroll_child(self, x, y, kind, modifiers);
+    }
+
+    /// A collapsed section with focus only shows its title, so enter expands
+    /// it instead of reaching the child
+    fn handle_key(&mut self, event: KeyEvent) -> EventResult {
+        // Send key event to the child with (partial) focus
+        let Some(index) = get_focused_index(&self.children) else {
+            // If nothing has focus, check if the right keys were pressed
+            match event.code {
+                KeyCode::Enter | KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {}
+                _ => return EventResult::Ignored,
+            }
+            // Focus the first section that can take focus
+            return match (0..self.children.len()).find(|&i| self.focus_section(i)) {
+                Some(_) => EventResult::Redraw,
+                None => EventResult::Ignored,
+            };
+        };
+        if event.code == KeyCode::Enter && !self.expanded.contains(&index) {
+            return match self.expand_index(index) {
+                Ok(_) => EventResult::Redraw,
+                Err(_) => EventResult::Ignored,
+            };
+        }
+        match self.children[index].as_base_mut().handle_key(event) {
+            EventResult::Border(border) => self.handle_focus_move(index, border),
+            result => result,
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) -> EventResult {
+        paste_focused(&mut self.children, text)
+    }
+
+    fn handle_terminal_focus(&mut self, focused: bool) {
+        for_each_child(&mut self.children, |c| c.handle_terminal_focus(focused));
+    }
+
+    fn handle_update(&mut self) {
+        for_each_child(&mut self.children, |c| c.handle_update());
+    }
+
+    fn invalidate(&mut self) {
+        for_each_child(&mut self.children, |c| c.invalidate());
+    }
+
+    fn resize(&mut self, width: u16, height: u16) -> Result<(), ResizeError> {
+        if self.width == width && self.height == height {
+            return Ok(());
+        }
+        self.resize_children(width, height)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        assert_eq!(area.width, self.width);
+        assert_eq!(area.height, self.height);
+        self.render_titles(area, buf);
+        let child_rects = self.as_container().get_children_rectangles();
+        for &i in &self.expanded {
+            self.children[i].as_base_mut().render(
+                Rect {
+                    x: child_rects[i].x + area.x,
+                    y: child_rects[i].y + area.y,
+                    height: child_rects[i].height,
+                    width: child_rects[i].width,
+                },
+                buf,
+            );
+        }
+    }
+
+    fn get_width(&self) -> u16 {
+        self.width
+    }
+
+    fn get_height(&self) -> u16 {
+        self.height
+    }
+
+    fn is_fixed_width(&self) -> bool {
+        false
+    }
+
+    fn is_fixed_height(&self) -> bool {
+        false
+    }
+
+    /// Every section needs a row for its title, and the expanded sections
+    /// need room for their child below it
+    fn get_size_range(&self, direction: Direction) -> (u16, u16) {
+        let min = match direction {
+            Direction::Horizontal => self
+                .children
+                .iter()
+                .map(|c| c.as_base().get_size_range(Direction::Horizontal).0)
+                .max()
+                .unwrap_or(0),
+            Direction::Vertical => {
+                self.expanded
+                    .iter()
+                    .fold(self.children.len() as u16, |min, &i| {
+                        min.saturating_add(
+                            self.children[i]
+                                .as_base()
+                                .get_size_range(Direction::Vertical)
+                                .0,
+                        )
+                    })
+            }
+        };
+        (min, u16::MAX)
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.children.iter().any(|c| c.as_base().is_focusable())
+    }
+
+    fn get_focus(&self) -> Focus {
+        get_children_focus(&self.children)
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_border(&self, x: u16, y: u16) -> Option<Border> {
+        let index = get_child_index(self, x, y)?;
+        let rect = self.as_container().get_children_rectangles()[index];
+        let border = self.children[index]
+            .as_base()
+            .get_border(x - rect.x, y - rect.y)?;
+        self.get_outer_border(rect, border)
+    }
+
+    fn get_border_along(&self, x: u16, y: u16, direction: Direction) -> Option<Border> {
+        let index = get_child_index(self, x, y)?;
+        let rect = self.as_container().get_children_rectangles()[index];
+        let border =
+            self.children[index]
+                .as_base()
+                .get_border_along(x - rect.x, y - rect.y, direction)?;
+        self.get_outer_border(rect, border)
+    }
+}
@@ -0,0 +1,823 @@
+use crossterm::event::{KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+use tui::{
+    buffer::Buffer,
+    layout::{Direction, Rect},
+};
+
+use crate::{container::list::*, container::*, Border, EventResult, Focus, MenuItem, ResizeError};
+
+/// Algorithms used to arrange the children of an automatic container
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AutoLayout {
+    /// One large child on the left with the rest stacked on the right
+    MasterStack,
+    /// Children side by side in columns of the same width
+    EvenHorizontal,
+    /// Children on top of each other in rows of the same height
+    EvenVertical,
+    /// Children in a grid with as many columns as rows
+    Tiled,
+    /// Each child takes half of the space left by the one before it, turning
+    /// clockwise around the container
+    Spiral,
+}
+
+impl AutoLayout {
+    /// Returns the layout after this one, wrapping back to the first
+    pub fn next(&self) -> Self {
+        match self {
+            Self::MasterStack => Self::EvenHorizontal,
+            Self::EvenHorizontal => Self::EvenVertical,
+            Self::EvenVertical => Self::Tiled,
+            Self::Tiled => Self::Spiral,
+            Self::Spiral => Self::MasterStack,
+        }
+    }
+}
+
+/// Splits the total into count sizes, with any remainder going to the last
+pub(crate) fn split_even(total: u16, count: usize) -> Vec<u16> {
+    let size = total / count as u16;
+    let mut sizes = vec![size; count];
+    sizes[count - 1] = total - size * (count as u16 - 1);
+    sizes
+}
+
+/// Splits the total into sizes for each child, giving the fixed children their
+/// size and the rest an even share of what is left, or splitting evenly if the
+/// fixed children do not fit
+fn split_fixed(total: u16, fixed: &[Option<u16>]) -> Vec<u16> {
+    let fixed_total = fixed.iter().flatten().map(|s| *s as u32).sum::<u32>();
+    let flexible = fixed.iter().filter(|s| s.is_none()).count();
+    let fits = match flexible {
+        0 => fixed_total == total as u32,
+        _ => fixed_total <= total as u32,
+    };
+    if flexible == fixed.len() || !fits {
+        return split_even(total, fixed.len());
+    }
+    let mut shares = match flexible {
+        0 => Vec::new(),
+        _ => split_even(total - fixed_total as u16, flexible),
+    }
+    .into_iter();
+    fixed
+        .iter()
+        .map(|s| s.unwrap_or_else(|| shares.next().unwrap_or(0)))
+        .collect()
+}
+
+/// Splits the area into a column for each child, of the same width apart from
+/// the children with a fixed width
+fn columns(area: Rect, fixed: &[Option<u16>]) -> Vec<Rect> {
+    let mut x = area.x;
+    split_fixed(area.width, fixed)
+        .into_iter()
+        .map(|width| {
+            x += width;
+            Rect {
+                x: x - width,
+                width,
+                ..area
+            }
+        })
+        .collect()
+}
+
+/// Splits the area into a row for each child, of the same height apart from
+/// the children with a fixed height
+fn rows(area: Rect, fixed: &[Option<u16>]) -> Vec<Rect> {
+    let mut y = area.y;
+    split_fixed(area.height, fixed)
+        .into_iter()
+        .map(|height| {
+            y += height;
+            Rect {
+                y: y - height,
+                height,
+                ..area
+            }
+        })
+        .collect()
+}
+
+/// Container arranging a flat list of children with a layout algorithm,
+/// recalculated whenever children are added or removed
+pub struct ContainerAuto {
+    name: String,
+    layout: AutoLayout,
+    master_ratio: f64,
+    flipped: bool,
+    master_drag: Option<(u16, u16)>,
+    capture: Option<usize>,
+    width: u16,
+    height: u16,
+    children: Vec<ContainerChild>,
+    menu_items: Vec<MenuItem>,
+}
+
+impl ContainerAuto {
+    pub fn new(name: String, layout: AutoLayout, width: u16, height: u16) -> Self {
+        Self {
+            name,
+            layout,
+            master_ratio: 0.5,
+            flipped: false,
+            master_drag: None,
+            capture: None,
+            width,
+            height,
+            children: Vec::new(),
+            menu_items: Vec::new(),
+        }
+    }
+
+    /// Adds a new component to the container, arranging the children again to
+    /// fit the new component, and returns an error without adding it if there
+    /// was no room available for the component
+    pub fn add_component(&mut self, child: Component) -> Result<(), ResizeError> {
+        self.add_child(ContainerChild::Component(child))
+    }
+
+    /// Adds a new container to the container, arranging the children again to
+    /// fit the new container, and returns an error without adding it if there
+    /// was no room available for the container
+    pub fn add_container(&mut self, child: Box<dyn Container>) -> Result<(), ResizeError> {
+        self.add_child(ContainerChild::Container(child))
+    }
+
+    fn add_child(&mut self, child: ContainerChild) -> Result<(), ResizeError> {
+        self.children.push(child);
+        if let Err(err) = self.refresh() {
+            self.children.pop();
+            let _ = self.refresh();
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    pub fn from_children(mut self, children: Vec<ContainerChild>) -> Result<Self, ResizeError> {
+        for child in children {
+            self.children.push(child);
+        }
+        self.refresh()?;
+        Ok(self)
+    }
+
+    pub fn get_layout(&self) -> AutoLayout {
+        self.layout.clone()
+    }
+
+    /// Sets the algorithm used to arrange the children, keeping the previous
+    /// layout if the children do not fit
+    pub fn set_layout(&mut self, layout: AutoLayout) -> Result<(), ResizeError> {
+        let previous = std::mem::replace(&mut self.layout, layout);
+        if let Err(err) = self.refresh() {
+            self.layout = previous;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    pub fn layout(mut self, layout: AutoLayout) -> Result<Self, ResizeError> {
+        self.set_layout(layout)?;
+        Ok(self)
+    }
+
+    pub fn get_master_ratio(&self) -> f64 {
+        self.master_ratio
+    }
+
+    /// Sets the share of the width taken by the first child in the master and
+    /// stack layout
+    pub fn set_master_ratio(&mut self, master_ratio: f64) -> Result<(), ResizeError> {
+        let previous = self.master_ratio;
+        self.master_ratio = master_ratio.clamp(0.0, 1.0);
+        if let Err(err) = self.refresh() {
+            self.master_ratio = previous;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    pub fn master_ratio(mut self, master_ratio: f64) -> Result<Self, ResizeError> {
+        self.set_master_ratio(master_ratio)?;
+        Ok(self)
+    }
+
+    /// Sets the context menu items shown for right clicks on any child
+    pub fn set_menu_items(&mut self, menu_items: Vec<MenuItem>) {
+        self.menu_items = menu_items;
+    }
+
+    pub fn menu_items(mut self, menu_items: Vec<MenuItem>) -> Self {
+        self.set_menu_items(menu_items);
+        self
+    }
+
+    /// Returns the width of the first child in the master and stack layout
+    /// for the width of the container
+    fn get_master_width(&self, width: u16) -> u16 {
+        let master_width = (width as f64 * self.master_ratio).round() as u16;
+        master_width.clamp(1, std::cmp::max(width, 2) - 1)
+    }
+
+    /// Calculates where each child goes in the layout for the size, swapping
+    /// the width and height when flipped
+    fn get_layout_rects(&self, width: u16, height: u16) -> Vec<Rect> {
+        let count = self.children.len();
+        if count == 0 {
+            return Vec::new();
+        }
+        let (width, height) = match self.flipped {
+            true => (height, width),
+            false => (width, height),
+        };
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        };
+        let fixed_widths = self.get_fixed_sizes(Direction::Horizontal);
+        let fixed_heights = self.get_fixed_sizes(Direction::Vertical);
+        let rects = match self.layout {
+            AutoLayout::EvenHorizontal => columns(area, &fixed_widths),
+            AutoLayout::EvenVertical => rows(area, &fixed_heights),
+            AutoLayout::MasterStack if count == 1 => vec![area],
+            AutoLayout::MasterStack => {
+                let master_width = self.get_master_width(width);
+                let stack = Rect {
+                    x: master_width,
+                    width: width - master_width,
+                    ..area
+                };
+                let mut rects = vec![Rect {
+                    width: master_width,
+                    ..area
+                }];
+                rects.extend(rows(stack, &fixed_heights[1..]));
+                rects
+            }
+            AutoLayout::Tiled => {
+                let num_columns = (count as f64).sqrt().ceil() as usize;
+                let num_rows = count.div_ceil(num_columns);
+                // The last row spreads out any children left over
+                rows(area, &vec![None; num_rows])
+                    .into_iter()
+                    .enumerate()
+                    .flat_map(|(i, row)| {
+                        let start = i * num_columns;
+                        let end = std::cmp::min(start + num_columns, count);
+                        columns(row, &fixed_widths[start..end])
+                    })
+                    .collect()
+            }
+            AutoLayout::Spiral => {
+                let mut rects = Vec::new();
+                let mut rest = area;
+                for i in 0..count - 1 {
+                    let (half_width, half_height) = (rest.width / 2, rest.height / 2);
+                    let (rect, next) = match i % 4 {
+                        0 => (
+                            Rect {
+                                width: half_width,
+                                ..rest
+                            },
+                            Rect {
+                                x: rest.x + half_width,
+                                width: rest.width - half_width,
+                                ..rest
+                            },
+                        ),
+                        1 => (
+                            Rect {
+                                height: half_height,
+                                ..rest
+                            },
+                            Rect {
+                                y: rest.y + half_height,
+                                height: rest.height - half_height,
+                                ..rest
+                            },
+                        ),
+                        2 => (
+                            Rect {
+                                x: rest.x + rest.width - half_width,
+                                width: half_width,
+                                ..rest
+                            },
+                            Rect {
+                                width: rest.width - half_width,
+                                ..rest
+                            },
+                        ),
+                        _ => (
+                            Rect {
+                                y: rest.y + rest.height - half_height,
+                                height: half_height,
+                                ..rest
+                            },
+                            Rect {
+                                height: rest.height - half_height,
+                                ..rest
+                            },
+                        ),
+                    };
+                    rects.push(rect);
+                    rest = next;
+                }
+                rects.push(rest);
+                rects
+            }
+        };
+        match self.flipped {
+            true => rects
+                .into_iter()
+                .map(|r| Rect {
+                    x: r.y,
+                    y: r.x,
+                    width: r.height,
+                    height: r.width,
+                })
+                .collect(),
+            false => rects,
+        }
+    }
+
+    /// Returns the direction in the layout before flipping for the direction
+    fn get_layout_direction(&self, direction: Direction) -> Direction {
+        match (self.flipped, direction) {
+            (false, direction) => direction,
+            (true, Direction::Horizontal) => Direction::Vertical,
+            (true, Direction::Vertical) => Direction::Horizontal,
+        }
+    }
+
+    /// Returns the size of each child along the direction in the layout before
+    /// flipping, or none for children without a fixed size along it
+    fn get_fixed_sizes(&self, direction: Direction) -> Vec<Option<u16>> {
+        let direction = self.get_layout_direction(direction);
+        self.children
+            .iter()
+            .map(|c| {
+                let c = c.as_base();
+                match direction {
+                    Direction::Horizontal => c.is_fixed_width().then(|| c.get_width()),
+                    Direction::Vertical => c.is_fixed_height().then(|| c.get_height()),
+                }
+            })
+            .collect()
+    }
+
+    /// Resizes all the children to their place in the layout for the new
+    /// size, rolling back to the previous sizes if any child fails to resize
+    fn resize_children(&mut self, width: u16, height: u16) -> Result<(), ResizeError> {
+        let rects = self.get_layout_rects(width, height);
+        // Fixed children keep their size, so fail if the layout has no place
+        // of that size for them
+        for (child, rect) in self.children.iter().zip(&rects) {
+            let child = child.as_base();
+            if (child.is_fixed_width() && child.get_width() != rect.width)
+                || (child.is_fixed_height() && child.get_height() != rect.height)
+            {
+                return Err(ResizeError {
+                    name: child.get_name(),
+                    width: rect.width,
+                    height: rect.height,
+                    border_width: 0,
+                });
+            }
+        }
+        let old_dimensions = self
+            .children
+            .iter()
+            .map(|c| (c.as_base().get_width(), c.as_base().get_height()))
+            .collect::<Vec<(u16, u16)>>();
+        for (i, rect) in rects.iter().enumerate() {
+            let result = self.children[i]
+                .as_base_mut()
+                .resize(rect.width, rect.height);
+            if let Err(err) = result {
+                for (i, dim) in old_dimensions.iter().enumerate() {
+                    let _ = self.children[i].as_base_mut().resize(dim.0, dim.1);
+                }
+                return Err(err);
+            }
+        }
+        self.width = width;
+        self.height = height;
+        self.capture = None;
+        self.invalidate();
+        Ok(())
+    }
+
+    /// Starts dragging the border between the master and the stack if the
+    /// mouse is on it in the master and stack layout
+    fn start_master_drag(&mut self, x: u16, y: u16) {
+        if self.layout != AutoLayout::MasterStack || self.children.len() < 2 {
+            return;
+        }
+        let Some(index) = get_child_index(self, x, y) else {
+            return;
+        };
+        let direction = self.get_layout_direction(Direction::Horizontal);
+        let child_rects = self.as_container().get_children_rectangles();
+        let rect = child_rects[index];
+        let border = self.children[index].as_base().get_border_along(
+            x - rect.x,
+            y - rect.y,
+            direction.clone(),
+        );
+        let (master_border, stack_border, mouse_offset, master_width) = match direction {
+            Direction::Horizontal => (Border::Right, Border::Left, x, child_rects[0].width),
+            Direction::Vertical => (Border::Bottom, Border::Top, y, child_rects[0].height),
+        };
+        let on_border = match index {
+            0 => border == Some(master_border),
+            _ => border == Some(stack_border),
+        };
+        if on_border {
+            self.master_drag = Some((mouse_offset, master_width));
+        }
+    }
+
+    /// Moves the border between the master and the stack along with the mouse
+    /// while dragging it, ending the drag on release
+    fn handle_master_drag(&mut self, mouse_offset: u16, kind: MouseEventKind) {
+        let Some((start_offset, start_width)) = self.master_drag else {
+            return;
+        };
+        let size = match self.get_layout_direction(Direction::Horizontal) {
+            Direction::Horizontal => self.width,
+            Direction::Vertical => self.height,
+        };
+        match kind {
+            MouseEventKind::Drag(_) if size > 1 => {
+                let master_width = start_width as i32 + mouse_offset as i32 - start_offset as i32;
+                let master_width = master_width.clamp(1, size as i32 - 1);
+                let _ = self.set_master_ratio(master_width as f64 / size as f64);
+            }
+            MouseEventKind::Up(_) => self.master_drag = None,
+            _ => {}
+        }
+    }
+
+    /// Returns the position of the mouse along the master and stack
+    fn get_mouse_offset(&self, x: i32, y: i32) -> u16 {
+        let mouse_offset = match self.get_layout_direction(Direction::Horizontal) {
+            Direction::Horizontal => x,
+            Direction::Vertical => y,
+        };
+        mouse_offset.clamp(0, u16::MAX as i32) as u16
+    }
+
+    /// Returns the border of the child if it is also on the outside of this
+    /// container
+    fn get_outer_border(&self, rect: Rect, border: Border) -> Option<Border> {
+        let outer = match border {
+            Border::Left => rect.x == 0,
+            Border::Top => rect.y == 0,
+            Border::Right => rect.x + rect.width >= self.width,
+            Border::Bottom => rect.y + rect.height >= self.height,
+        };
+        outer.then_some(border)
+    }
+}
+
+impl Container for ContainerAuto {
+    fn get_children(&self) -> &Vec<ContainerChild> {
+        &self.children
+    }
+
+    fn get_children_mut(&mut self) -> &mut Vec<ContainerChild> {
+        &mut self.children
+    }
+
+    fn get_children_rectangles(&self) -> Vec<Rect> {
+        self.get_layout_rects(self.width, self.height)
+    }
+
+    fn as_base(&self) -> &dyn ComponentBase {
+        self
+    }
+
+    fn as_base_mut(&mut self) -> &mut dyn ComponentBase {
+        self
+    }
+
+    /// Only the master and stack layout can be resized, by moving the border
+    /// between the first child and the rest
+    fn is_resizable(&self) -> bool {
+        self.layout == AutoLayout::MasterStack
+    }
+
+    fn refresh(&mut self) -> Result<(), ResizeError> {
+        // Nothing to lay out until the container has been sized
+        if self.width == 0 && self.height == 0 {
+            return Ok(());
+        }
+        self.resize_children(self.width, self.height)
+    }
+
+    fn get_menu_items(&self) -> Vec<MenuItem> {
+        self.menu_items.clone()
+    }
+
+    fn resize_child(&mut self, name: &str, border: Border, delta: i16) -> bool {
+        if !self.is_resizable() || self.children.len() < 2 {
+            return false;
+        }
+        let Some(index) = self
+            .children
+            .iter()
+            .position(|c| c.as_base().get_name() == name)
+        else {
+            return false;
+        };
+        // Growing the first child moves the border towards the stack
+        let delta = match (self.flipped, index, border) {
+            (false, 0, Border::Right) | (true, 0, Border::Bottom) => delta as i32,
+            (false, 1.., Border::Left) | (true, 1.., Border::Top) => -delta as i32,
+            _ => return false,
+        };
+        let width = match self.flipped {
+            true => self.height,
+            false => self.width,
+        };
+        let master_width = self.get_master_width(width) as i32;
+        let next_width = (master_width + delta).clamp(1, width as i32 - 1);
+        if next_width == master_width {
+            return false;
+        }
+        self.set_master_ratio(next_width as f64 / width as f64)
+            .is_ok()
+    }
+
+    fn equalize(&mut self, recursive: bool) -> Result<(), ResizeError> {
+        let master_ratio = self.master_ratio;
+        self.set_master_ratio(0.5)?;
+        if !recursive {
+            return Ok(());
+        }
+        for i in 0..self.children.len() {
+            let ContainerChild::Container(container) = &mut self.children[i] else {
+                continue;
+            };
+            if let Err(err) = container.equalize(true) {
+                let _ = self.set_master_ratio(master_ratio);
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    fn rotate(&mut self, reverse: bool) -> Result<(), ResizeError> {
+        let rotate = |children: &mut Vec<ContainerChild>, reverse: bool| match reverse {
+            true => children.rotate_left(1),
+            false => children.rotate_right(1),
+        };
+        rotate(&mut self.children, reverse);
+        if let Err(err) = self.refresh() {
+            rotate(&mut self.children, !reverse);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn flip(&mut self) -> Result<(), ResizeError> {
+        self.flipped = !self.flipped;
+        if let Err(err) = self.refresh() {
+            self.flipped = !self.flipped;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn next_layout(&mut self) -> Result<(), ResizeError> {
+        self.set_layout(self.layout.next())
+    }
+
+    fn remove_child(&mut self, name: &str) -> Option<ContainerChild> {
+        let index = self
+            .children
+            .iter()
+            .position(|c| c.as_base().get_name() == name)?;
+        let child = self.children.remove(index);
+        if self.refresh().is_err() {
+            self.children.insert(index, child);
+            let _ = self.refresh();
+            return None;
+        }
+        Some(child)
+    }
+
+    fn as_container(&self) -> &dyn Container {
+        self
+    }
+
+    fn as_container_mut(&mut self) -> &mut dyn Container {
+        self
+    }
+}
+
+impl ComponentBase for ContainerAuto {
+    fn handle_mouse(&mut self, x: u16, y: u16, kind: Option<MouseEventKind>) {
+        // Check if the mouse event is none
+        let Some(kind) = kind else {
+            // Issue none to all children
+            for_each_child(&mut self.children, |c| c.handle_mouse(0, 0, None));
+            self.master_drag = None;
+            self.capture = None;
+            return;
+        };
+        // Send mouse move events to the child under the mouse, letting the rest
+        // know the mouse left them without changing focus
+        if let MouseEventKind::Moved = kind {
+            hover_child(self, x, y);
+            return;
+        }
+        // Scroll events go to handle_scroll instead, which has the modifiers
+        if let MouseEventKind::ScrollDown | MouseEventKind::ScrollUp = kind {
+            return;
+        }
+        // Handle an ongoing drag of the border between the master and the stack
+        let mouse_offset = self.get_mouse_offset(x as i32, y as i32);
+        self.handle_master_drag(mouse_offset, kind);
+        // Send drags and the release to the child the drag started in
+        if let (Some(index), MouseEventKind::Drag(_) | MouseEventKind::Up(_)) = (self.capture, kind)
+        {
+            if let MouseEventKind::Up(_) = kind {
+                self.capture = None;
+            }
+            handle_captured_child(self, index, x as i32, y as i32, kind);
+            return;
+        }
+        let index = get_child_index(self, x, y);
+        if let MouseEventKind::Down(button) = kind {
+            self.capture = index;
+            if button == MouseButton::Left {
+                self.start_master_drag(x, y);
+            }
+        }
+        // Dispatch the mouse event to the child under the mouse, issuing none
+        // to the rest
+        dispatch_child(self, index, x, y, kind);
+    }
+
+    fn handle_mouse_leave(&mut self) {
+        for_each_child(&mut self.children, |c| c.handle_mouse_leave());
+    }
+
+    fn handle_mouse_captured(&mut self, x: i32, y: i32, kind: MouseEventKind) {
+        // Keep dragging the master border while outside of the container
+        let mouse_offset = self.get_mouse_offset(x, y);
+        self.handle_master_drag(mouse_offset, kind);
+        let Some(index) = self.capture else {
+            return;
+        };
+        if let MouseEventKind::Up(_) = kind {
+            self.capture = None;
+        }
+        handle_captured_child(self, index, x, y, kind);
+    }
+
+    fn handle_click(&mut self, x: u16, y: u16, button: MouseButton, count: u8) {
+        click_child(self, x, y, button, count);
+    }
+
+    fn handle_scroll(&mut self, x: u16, y: u16, kind: MouseEventKind, modifiers: KeyModifiers) {
+        scroll_child(self, x, y, kind, modifiers);
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> EventResult {
+        // Send key event to the child with (partial) focus, or find something
+        // to focus if nothing has focus
+        let Some(index) = get_focused_index(&self.children) else {
+            return focus_focusable(self, event);
+        };
+        match self.children[index].as_base_mut().handle_key(event) {
+            EventResult::Border(border) => move_focus(self, border),
+            result => result,
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) -> EventResult {
+        paste_focused(&mut self.children, text)
+    }
+
+    fn handle_terminal_focus(&mut self, focused: bool) {
+        for_each_child(&mut self.children, |c| c.handle_terminal_focus(focused));
+    }
+
+    fn handle_update(&mut self) {
+        for_each_child(&mut self.children, |c| c.handle_update());
+    }
+
+    fn invalidate(&mut self) {
+        for_each_child(&mut self.children, |c| c.invalidate());
+    }
+
+    fn resize(&mut self, width: u16, height: u16) -> Result<(), ResizeError> {
+        if self.width == width && self.height == height {
+            return Ok(());
+        }
+        self.resize_children(width, height)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        assert_eq!(area.width, self.width);
+        assert_eq!(area.height, self.height);
+        render_children(self, area, buf);
+    }
+
+    fn get_width(&self) -> u16 {
+        self.width
+    }
+
+    fn get_height(&self) -> u16 {
+        self.height
+    }
+
+    fn is_fixed_width(&self) -> bool {
+        !self.children.is_empty() && self.children.iter().all(|c| c.as_base().is_fixed_width())
+    }
+
+    fn is_fixed_height(&self) -> bool {
+        !self.children.is_empty() && self.children.iter().all(|c| c.as_base().is_fixed_height())
+    }
+
+    /// Even layouts add up the children along the layout, where only fixed
+    /// children differ in size, and give every child the whole size across it,
+    /// while the other layouts can give any child the whole size
+    fn get_size_range(&self, direction: Direction) -> (u16, u16) {
+        let ranges = self
+            .children
+            .iter()
+            .map(|c| c.as_base().get_size_range(direction.clone()))
+            .collect::<Vec<(u16, u16)>>();
+        let min = ranges.iter().map(|r| r.0).max().unwrap_or(0);
+        let even = match self.get_layout_direction(direction.clone()) {
+            Direction::Horizontal => self.layout == AutoLayout::EvenHorizontal,
+            Direction::Vertical => self.layout == AutoLayout::EvenVertical,
+        };
+        match self.layout {
+            _ if even => {
+                let fixed = self.get_fixed_sizes(direction);
+                let fixed_total = fixed.iter().flatten().map(|s| *s as u32).sum::<u32>();
+                let flexible = ranges
+                    .iter()
+                    .zip(&fixed)
+                    .filter(|(_, s)| s.is_none())
+                    .map(|(r, _)| *r)
+                    .collect::<Vec<(u16, u16)>>();
+                let count = flexible.len() as u32;
+                let flexible_min = flexible.iter().map(|r| r.0).max().unwrap_or(0) as u32;
+                let flexible_max = flexible.iter().map(|r| r.1).min().unwrap_or(0) as u32;
+                let min = fixed_total + count * flexible_min;
+                let max = fixed_total + count * flexible_max;
+                let max = std::cmp::max(max, min);
+                (
+                    min.min(u16::MAX as u32) as u16,
+                    max.min(u16::MAX as u32) as u16,
+                )
+            }
+            AutoLayout::EvenHorizontal | AutoLayout::EvenVertical => {
+                let max = ranges.iter().map(|r| r.1).min().unwrap_or(u16::MAX);
+                (min, std::cmp::max(max, min))
+            }
+            _ => (min, u16::MAX),
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.children.iter().any(|c| c.as_base().is_focusable())
+    }
+
+    fn get_focus(&self) -> Focus {
+        get_children_focus(&self.children)
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_border(&self, x: u16, y: u16) -> Option<Border> {
+        let index = get_child_index(self, x, y)?;
+        let rect = self.as_container().get_children_rectangles()[index];
+        let border = self.children[index]
+            .as_base()
+            .get_border(x - rect.x, y - rect.y)?;
+        self.get_outer_border(rect, border)
+    }
+
+    fn get_border_along(&self, x: u16, y: u16, direction: Direction) -> Option<Border> {
+        let index = get_child_index(self, x, y)?;
+        let rect = self.as_container().get_children_rectangles()[index];
+        let border =
+            self.children[index]
+                .as_base()
+                .get_border_along(x - rect.x, y - rect.y, direction)?;
+        self.get_outer_border(rect, border)
+    }
+}
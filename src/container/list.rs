@@ -1,42 +1,290 @@
-use std::cmp::Ordering;
-
-use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEventKind};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
 use tui::{
     buffer::Buffer,
     layout::{Direction, Rect},
+    style::{Color, Modifier, Style},
 };
 
 use crate::{
-    container::search::ContainerSearch, container::*, pos::*, Border, Focus, FocusResult,
-    ResizeError,
+    container::search::{search_focused_area, ContainerSearch},
+    container::*,
+    pos::*,
+    Border, EventResult, Focus, FocusResult, KeyHandler, MenuItem, ResizeError,
 };
 
-fn find_next_pos(
-    pos: ComponentPos,
+/// Finds the position of the next focusable component across the border of a
+/// component, skipping over any components that cannot take focus, where the
+/// component can be partly or fully outside of the container
+pub(crate) fn find_next_pos(
+    container: &dyn Container,
+    (x, y): (i32, i32),
     border: Border,
     component_width: u16,
     component_height: u16,
-    container_width: u16,
-    container_height: u16,
 ) -> Option<ComponentPos> {
-    match border {
-        Border::Top if pos.y > 0 => Some(ComponentPos {
-            x: pos.x,
-            y: pos.y - 1,
-        }),
-        Border::Bottom if pos.y + component_height < container_height => Some(ComponentPos {
-            x: pos.x,
-            y: pos.y + component_height,
-        }),
-        Border::Left if pos.x > 0 => Some(ComponentPos {
-            x: pos.x - 1,
-            y: pos.y,
-        }),
-        Border::Right if pos.x + component_width < container_width => Some(ComponentPos {
-            x: pos.x + component_width,
-            y: pos.y,
-        }),
-        _ => None,
+    let (container_width, container_height) =
+        (container.get_width() as i32, container.get_height() as i32);
+    if container_width == 0 || container_height == 0 {
+        return None;
+    }
+    let (width, height) = (component_width as i32, component_height as i32);
+    let (next_x, next_y) = match border {
+        Border::Top if y > 0 => (x, std::cmp::min(y - 1, container_height - 1)),
+        Border::Bottom if y + height < container_height => (x, std::cmp::max(y + height, 0)),
+        Border::Left if x > 0 => (std::cmp::min(x - 1, container_width - 1), y),
+        Border::Right if x + width < container_width => (std::cmp::max(x + width, 0), y),
+        _ => return None,
+    };
+    // Search along the edge of the part of the component in the container
+    let next_pos = ComponentPos {
+        x: next_x.clamp(0, container_width - 1) as u16,
+        y: next_y.clamp(0, container_height - 1) as u16,
+    };
+    let Some((component, component_pos)) = container.search_position(next_pos.clone()) else {
+        // Step over the gaps between components left for splitters
+        let pos = (next_pos.x as i32, next_pos.y as i32);
+        return find_next_pos(container, pos, border, 1, 1);
+    };
+    if component.is_focusable() {
+        return Some(next_pos);
+    }
+    // Keep looking past the component that cannot take focus
+    let pos = match border {
+        Border::Top | Border::Bottom => (next_pos.x as i32, component_pos.y as i32),
+        Border::Left | Border::Right => (component_pos.x as i32, next_pos.y as i32),
+    };
+    find_next_pos(
+        container,
+        pos,
+        border,
+        component.get_width(),
+        component.get_height(),
+    )
+}
+
+/// Returns the position and size of the focused component, with the position
+/// outside of the container for components scrolled out of view
+pub(crate) fn get_focused_position(container: &dyn Container) -> Option<((i32, i32), u16, u16)> {
+    search_focused_area(container).map(|(x, y, width, height)| ((x, y), width, height))
+}
+
+/// Moves focus from the focused component to the next component across the
+/// border, returning the border if there is no such component in the container
+pub(crate) fn move_focus(container: &mut dyn Container, border: Border) -> EventResult {
+    let Some((pos, width, height)) = get_focused_position(container) else {
+        return EventResult::Ignored;
+    };
+    let Some(next_pos) = find_next_pos(container, pos, border.clone(), width, height) else {
+        return EventResult::Border(border);
+    };
+    focus_position(container, next_pos);
+    EventResult::Redraw
+}
+
+/// Clears the focus of the focused component, if any, and partially focuses
+/// the component at the position
+pub(crate) fn focus_position(container: &mut dyn Container, pos: ComponentPos) {
+    clear_focus(container);
+    if let Some((component, _)) = container.search_position_mut(pos) {
+        component.set_focus(Focus::PartialFocus);
+    }
+}
+
+/// Clears the focus of the focused component, if any
+pub(crate) fn clear_focus(container: &mut dyn Container) {
+    if let FocusResult::Focus((component, _)) | FocusResult::PartialFocus((component, _)) =
+        container.search_focused_mut()
+    {
+        component.set_focus(Focus::None);
+    }
+}
+
+/// Clears the focus of every other child if the child at the index has focus
+pub(crate) fn focus_only_if_focused(children: &mut [ContainerChild], index: usize) {
+    if children[index].as_base().get_focus() != Focus::Focus {
+        return;
+    }
+    for (i, child) in children.iter_mut().enumerate() {
+        if i != index {
+            child.as_base_mut().handle_mouse(0, 0, None);
+        }
+    }
+}
+
+/// Returns the index of the child at the position
+pub(crate) fn get_child_index(container: &dyn Container, x: u16, y: u16) -> Option<usize> {
+    let pos = ComponentPos { x, y };
+    container
+        .get_children_rectangles()
+        .iter()
+        .position(|r| pos.intersects_rect(*r))
+}
+
+/// Sends a mouse move to the child under the mouse, letting the rest know the
+/// mouse left them, and takes focus from the rest if hovering focused the child
+pub(crate) fn hover_child(container: &mut dyn Container, x: u16, y: u16) {
+    let child_rects = container.get_children_rectangles();
+    let hovered = get_child_index(container, x, y);
+    let mut newly_focused = false;
+    for (i, child) in container.get_children_mut().iter_mut().enumerate() {
+        if hovered == Some(i) {
+            let (child_x, child_y) = (x - child_rects[i].x, y - child_rects[i].y);
+            let focused = child.as_base().get_focus() == Focus::Focus;
+            child
+                .as_base_mut()
+                .handle_mouse(child_x, child_y, Some(MouseEventKind::Moved));
+            newly_focused = !focused;
+        } else {
+            child.as_base_mut().handle_mouse_leave();
+        }
+    }
+    if let (Some(index), true) = (hovered, newly_focused) {
+        focus_only_if_focused(container.get_children_mut(), index);
+    }
+}
+
+/// Sends the mouse event to the child at the index, issuing none to the rest
+pub(crate) fn dispatch_child(
+    container: &mut dyn Container,
+    index: Option<usize>,
+    x: u16,
+    y: u16,
+    kind: MouseEventKind,
+) {
+    let child_rects = container.get_children_rectangles();
+    for (i, child) in container.get_children_mut().iter_mut().enumerate() {
+        if index != Some(i) {
+            child.as_base_mut().handle_mouse(0, 0, None);
+            continue;
+        }
+        let (child_x, child_y) = (x - child_rects[i].x, y - child_rects[i].y);
+        child
+            .as_base_mut()
+            .handle_mouse(child_x, child_y, Some(kind));
+    }
+}
+
+/// Sends a drag or release to the child that captured the mouse, wherever the
+/// mouse is relative to that child
+pub(crate) fn handle_captured_child(
+    container: &mut dyn Container,
+    index: usize,
+    x: i32,
+    y: i32,
+    kind: MouseEventKind,
+) {
+    let Some(rect) = container.get_children_rectangles().get(index).cloned() else {
+        return;
+    };
+    container.get_children_mut()[index]
+        .as_base_mut()
+        .handle_mouse_captured(x - rect.x as i32, y - rect.y as i32, kind);
+}
+
+/// Sends a click to the child under the mouse
+pub(crate) fn click_child(
+    container: &mut dyn Container,
+    x: u16,
+    y: u16,
+    button: MouseButton,
+    count: u8,
+) {
+    let Some(index) = get_child_index(container, x, y) else {
+        return;
+    };
+    let rect = container.get_children_rectangles()[index];
+    container.get_children_mut()[index]
+        .as_base_mut()
+        .handle_click(x - rect.x, y - rect.y, button, count);
+}
+
+/// Sends a scroll to the child under the mouse, taking focus from the other
+/// children if scrolling focused the child
+pub(crate) fn scroll_child(
+    container: &mut dyn Container,
+    x: u16,
+    y: u16,
+    kind: MouseEventKind,
+    modifiers: KeyModifiers,
+) {
+    let Some(index) = get_child_index(container, x, y) else {
+        return;
+    };
+    let rect = container.get_children_rectangles()[index];
+    let children = container.get_children_mut();
+    let child = children[index].as_base_mut();
+    let focused = child.get_focus() == Focus::Focus;
+    child.handle_scroll(x - rect.x, y - rect.y, kind, modifiers);
+    if !focused {
+        focus_only_if_focused(children, index);
+    }
+}
+
+/// Partially focuses the first focusable component when one of the keys that
+/// move focus is pressed while nothing has focus
+pub(crate) fn focus_focusable(container: &mut dyn Container, event: KeyEvent) -> EventResult {
+    match event.code {
+        KeyCode::Enter | KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {}
+        _ => return EventResult::Ignored,
+    }
+    let Some((_, pos)) = container.search_focusable() else {
+        return EventResult::Ignored;
+    };
+    if let Some((component, _)) = container.search_position_mut(pos) {
+        component.set_focus(Focus::PartialFocus);
+    }
+    EventResult::Redraw
+}
+
+/// Returns the index of the child with (partial) focus
+pub(crate) fn get_focused_index(children: &[ContainerChild]) -> Option<usize> {
+    children
+        .iter()
+        .position(|c| c.as_base().get_focus() != Focus::None)
+}
+
+/// Sends pasted text to the child with (partial) focus
+pub(crate) fn paste_focused(children: &mut [ContainerChild], text: &str) -> EventResult {
+    let focused = children
+        .iter_mut()
+        .find(|c| c.as_base().get_focus() != Focus::None);
+    match focused {
+        Some(child) => child.as_base_mut().handle_paste(text),
+        None => EventResult::Ignored,
+    }
+}
+
+/// Calls the function on every child
+pub(crate) fn for_each_child<F>(children: &mut [ContainerChild], mut f: F)
+where
+    F: FnMut(&mut dyn ComponentBase),
+{
+    for child in children {
+        f(child.as_base_mut());
+    }
+}
+
+/// Returns the focus of the first child with (partial) focus
+pub(crate) fn get_children_focus(children: &[ContainerChild]) -> Focus {
+    children
+        .iter()
+        .map(|c| c.as_base().get_focus())
+        .find(|focus| *focus != Focus::None)
+        .unwrap_or(Focus::None)
+}
+
+/// Renders every child in its rectangle, offset by the area of the container
+pub(crate) fn render_children(container: &mut dyn Container, area: Rect, buf: &mut Buffer) {
+    let child_rects = container.get_children_rectangles();
+    for (child, rect) in container.get_children_mut().iter_mut().zip(child_rects) {
+        child.as_base_mut().render(
+            Rect {
+                x: rect.x + area.x,
+                y: rect.y + area.y,
+                ..rect
+            },
+            buf,
+        );
     }
 }
 
@@ -82,9 +330,14 @@ pub struct ContainerList {
     orientation: Direction,
     resizable: bool,
     resize: Resize,
+    capture: Option<usize>,
     width: u16,
     height: u16,
     children: Vec<ContainerChild>,
+    key_handler: Option<KeyHandler>,
+    menu_items: Vec<MenuItem>,
+    splitter: Option<char>,
+    splitter_hover: Option<usize>,
 }
 
 impl ContainerList {
@@ -100,9 +353,14 @@ impl ContainerList {
             orientation,
             resizable,
             resize: Resize::None,
+            capture: None,
             width,
             height,
             children: Vec::new(),
+            key_handler: None,
+            menu_items: Vec::new(),
+            splitter: None,
+            splitter_hover: None,
         }
     }
 
@@ -124,6 +382,32 @@ impl ContainerList {
                 Direction::Vertical => SizingConstraint::Ratio(c.get_height() as f64),
             })
             .collect::<Vec<SizingConstraint>>();
+        Some(ContainerList::normalize_constraints(constraints))
+    }
+
+    /// Gets the constraints of the children with the given ratios, keeping the
+    /// fixed size of any child fixed along the orientation
+    fn get_ratio_constraints(&self, ratios: Vec<f64>) -> Vec<SizingConstraint> {
+        let constraints = self
+            .children
+            .iter()
+            .map(|c| c.as_base())
+            .zip(ratios)
+            .map(|(c, r)| match self.orientation {
+                Direction::Horizontal if c.is_fixed_width() => {
+                    SizingConstraint::Fixed(c.get_width())
+                }
+                Direction::Vertical if c.is_fixed_height() => {
+                    SizingConstraint::Fixed(c.get_height())
+                }
+                _ => SizingConstraint::Ratio(r),
+            })
+            .collect::<Vec<SizingConstraint>>();
+        ContainerList::normalize_constraints(constraints)
+    }
+
+    /// Scales the ratio constraints to add up to one
+    fn normalize_constraints(constraints: Vec<SizingConstraint>) -> Vec<SizingConstraint> {
         // Calculates the total size/count of all ratio controlled components
         let total_ratio = constraints
             .iter()
@@ -156,10 +440,11 @@ impl ContainerList {
                 SizingConstraint::Ratio(r) => SizingConstraint::Ratio(r / total_ratio),
             })
             .collect::<Vec<SizingConstraint>>();
-        Some(constraints)
+        constraints
     }
 
     /// Sets all the children to be proportioned sizes in the container
+    #[allow(clippy::useless_vec)]
     fn calculate_sizes(constraints: Vec<SizingConstraint>, size: u16) -> Option<Vec<u16>> {
         // Splits the constraints into fixed and ratio lists
         let fixed_constraints = constraints
@@ -213,17 +498,23 @@ impl ContainerList {
     /// Adds a new component to the container, resizes the existing children
     /// to fit the new component, and returns false if there was no room
     /// available for the component
+    ///
+    /// Layout is deferred until the first resize while the container is still
+    /// zero by zero, so no error is returned for a container not yet sized
     pub fn add_component(&mut self, child: Component) -> Result<(), ResizeError> {
         self.children.push(ContainerChild::Component(child));
-        self.resize(self.get_width(), self.get_height())
+        self.refresh()
     }
 
     /// Adds a new container to the container, resizes the existing children
     /// to fit the new container, and returns false if there was no room
     /// available for the container
+    ///
+    /// Layout is deferred until the first resize while the container is still
+    /// zero by zero, so no error is returned for a container not yet sized
     pub fn add_container(&mut self, child: Box<dyn Container>) -> Result<(), ResizeError> {
         self.children.push(ContainerChild::Container(child));
-        self.resize(self.get_width(), self.get_height())
+        self.refresh()
     }
 
     pub fn from_children(mut self, children: Vec<ContainerChild>) -> Result<Self, ResizeError> {
@@ -234,126 +525,322 @@ impl ContainerList {
         Ok(self)
     }
 
+    /// Resizes all the children to fit the new size, rolling back to the
+    /// previous sizes if any child fails to resize
+    fn resize_children(&mut self, width: u16, height: u16) -> Result<(), ResizeError> {
+        // Get constraints for resizing later
+        let Some(constraints) = self.get_sizing_constraints() else {
+            return Err(ResizeError {
+                name: self.get_name(),
+                width,
+                height,
+                border_width: 0,
+            });
+        };
+        self.resize_children_with(constraints, width, height)
+    }
+
+    /// Resizes all the children to fit the size following the constraints,
+    /// rolling back to the previous sizes if any child fails to resize
+    fn resize_children_with(
+        &mut self,
+        constraints: Vec<SizingConstraint>,
+        width: u16,
+        height: u16,
+    ) -> Result<(), ResizeError> {
+        // Get current sizing
+        let old_dimensions = self
+            .children
+            .iter()
+            .map(|c| (c.as_base().get_width(), c.as_base().get_height()))
+            .collect::<Vec<(u16, u16)>>();
+        // Calculate new sizing in the space left between the splitters
+        let size = match self.orientation {
+            Direction::Horizontal => width,
+            Direction::Vertical => height,
+        };
+        let new_sizes = size
+            .checked_sub(self.get_splitters_size())
+            .and_then(|size| ContainerList::calculate_sizes(constraints, size));
+        let Some(new_sizes) = new_sizes else {
+            return Err(ResizeError {
+                name: self.get_name(),
+                width,
+                height,
+                border_width: 0,
+            });
+        };
+        let new_sizes = new_sizes
+            .iter()
+            .map(|s| match self.orientation {
+                Direction::Horizontal => (*s, height),
+                Direction::Vertical => (width, *s),
+            })
+            .collect::<Vec<(u16, u16)>>();
+        for (i, size) in new_sizes.iter().enumerate().take(self.children.len()) {
+            if let Err(err) = self.children[i].as_base_mut().resize(size.0, size.1) {
+                for (i, dim) in old_dimensions.iter().enumerate().take(self.children.len()) {
+                    let _ = self.children[i].as_base_mut().resize(dim.0, dim.1);
+                }
+                return Err(err);
+            }
+        }
+        self.width = width;
+        self.height = height;
+        self.invalidate();
+        Ok(())
+    }
+
+    /// Returns the sizes of the children along the orientation
+    fn get_sizes_along(&self) -> Vec<f64> {
+        self.children
+            .iter()
+            .map(|c| match self.orientation {
+                Direction::Horizontal => c.as_base().get_width() as f64,
+                Direction::Vertical => c.as_base().get_height() as f64,
+            })
+            .collect()
+    }
+
+    /// Resizes the children with the given ratios, skipped if the container
+    /// has not been sized yet
+    fn refresh_with_ratios(&mut self, ratios: Vec<f64>) -> Result<(), ResizeError> {
+        if self.width == 0 && self.height == 0 {
+            return Ok(());
+        }
+        let constraints = self.get_ratio_constraints(ratios);
+        self.resize = Resize::None;
+        self.capture = None;
+        self.resize_children_with(constraints, self.width, self.height)
+    }
+
+    /// Sets the handler for keys ignored by the focused children
+    pub fn set_key_handler(&mut self, key_handler: Option<KeyHandler>) {
+        self.key_handler = key_handler;
+    }
+
+    pub fn key_handler(mut self, key_handler: Option<KeyHandler>) -> Self {
+        self.set_key_handler(key_handler);
+        self
+    }
+
+    /// Sets the context menu items shown for right clicks on any child
+    pub fn set_menu_items(&mut self, menu_items: Vec<MenuItem>) {
+        self.menu_items = menu_items;
+    }
+
+    pub fn menu_items(mut self, menu_items: Vec<MenuItem>) -> Self {
+        self.set_menu_items(menu_items);
+        self
+    }
+
+    pub fn get_splitter(&self) -> Option<char> {
+        self.splitter
+    }
+
+    /// Sets the glyph of the one cell splitter drawn between each child, which
+    /// can be dragged to resize the children on either side even when they
+    /// have no borders
+    pub fn set_splitter(&mut self, splitter: Option<char>) -> Result<(), ResizeError> {
+        let previous = std::mem::replace(&mut self.splitter, splitter);
+        self.splitter_hover = None;
+        if let Err(err) = self.refresh() {
+            self.splitter = previous;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    pub fn splitter(mut self, splitter: Option<char>) -> Result<Self, ResizeError> {
+        self.set_splitter(splitter)?;
+        Ok(self)
+    }
+
+    /// Returns the total size taken up by the splitters along the orientation
+    fn get_splitters_size(&self) -> u16 {
+        match self.splitter {
+            Some(_) => self.children.len().saturating_sub(1) as u16,
+            None => 0,
+        }
+    }
+
+    /// Returns the index of the child before the splitter at the position
+    fn get_splitter_index(&self, x: u16, y: u16) -> Option<usize> {
+        self.splitter?;
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let child_rects = self.as_container().get_children_rectangles();
+        let len = child_rects.len();
+        child_rects
+            .iter()
+            .take(len.saturating_sub(1))
+            .position(|r| match self.orientation {
+                Direction::Horizontal => x == r.x + r.width,
+                Direction::Vertical => y == r.y + r.height,
+            })
+    }
+
+    /// Renders the splitters between the children, highlighting the one under
+    /// the mouse
+    fn render_splitters(&self, area: Rect, buf: &mut Buffer) {
+        let Some(splitter) = self.splitter else {
+            return;
+        };
+        let child_rects = self.as_container().get_children_rectangles();
+        let len = child_rects.len();
+        for (i, r) in child_rects.iter().take(len.saturating_sub(1)).enumerate() {
+            let rect = match self.orientation {
+                Direction::Horizontal => Rect {
+                    x: area.x + r.x + r.width,
+                    width: 1,
+                    ..area
+                },
+                Direction::Vertical => Rect {
+                    y: area.y + r.y + r.height,
+                    height: 1,
+                    ..area
+                },
+            };
+            let style = if self.splitter_hover == Some(i) {
+                Style::default()
+                    .fg(Color::LightCyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            for x in rect.x..(rect.x + rect.width) {
+                for y in rect.y..(rect.y + rect.height) {
+                    buf.get_mut(x, y).set_char(splitter).set_style(style);
+                }
+            }
+        }
+    }
+
     pub fn get_orientation(&self) -> Direction {
         self.orientation.clone()
     }
 
-    fn handle_resize(
-        &mut self,
-        mouse_offset_next: u16,
-        orientation: Direction,
-        kind: MouseEventKind,
-    ) {
+    fn handle_resize(&mut self, mouse_offset_next: u16, kind: MouseEventKind) {
         // Clear resizing if not resizable or mouse event is not left drag
         if !self.resizable || kind != MouseEventKind::Drag(MouseButton::Left) {
             self.resize = Resize::None;
             return;
         }
-        // Get indices of affected child components and the drag delta
-        let (index0, index1, delta) = match self.resize {
+        // Get the divider being dragged and the drag delta
+        let (divider, mouse_offset) = match self.resize {
             Resize::LeftTop {
                 mouse_offset,
                 child_index,
-            } => (
-                child_index - 1,
-                child_index,
-                mouse_offset_next as i16 - mouse_offset as i16,
-            ),
+            } => (child_index - 1, mouse_offset),
             Resize::RightBottom {
                 mouse_offset,
                 child_index,
-            } => (
-                child_index,
-                child_index + 1,
-                mouse_offset_next as i16 - mouse_offset as i16,
-            ),
+            } => (child_index, mouse_offset),
             Resize::None => return,
         };
-        // Make sure that this will not resize a fixed size component on either side
-        match orientation {
-            Direction::Horizontal
-                if (self.get_children()[index0].as_base().is_fixed_width()
-                    || self.get_children()[index1].as_base().is_fixed_width()) =>
-            {
-                return
-            }
-            Direction::Vertical
-                if (self.get_children()[index0].as_base().is_fixed_height()
-                    || self.get_children()[index1].as_base().is_fixed_height()) =>
-            {
-                return
-            }
-            _ => {}
-        }
-        // Get current sizes of child components
-        let (width0, height0, width1, height1) = (
-            self.get_children()[index0].as_base().get_width(),
-            self.get_children()[index0].as_base().get_height(),
-            self.get_children()[index1].as_base().get_width(),
-            self.get_children()[index1].as_base().get_height(),
-        );
-        // Use orientation and drag to calculate new width/height
-        let (width0, height0, width1, height1) = match orientation {
-            Direction::Horizontal => {
-                let width0 = width0 as i16 + delta;
-                let width1 = width1 as i16 - delta;
-                if width0 <= 0 || width1 <= 0 {
-                    self.resize = Resize::None;
-                    return;
-                }
-                (width0 as u16, height0, width1 as u16, height1)
-            }
-            Direction::Vertical => {
-                let height0 = height0 as i16 + delta;
-                let height1 = height1 as i16 - delta;
-                if height0 <= 0 || height1 <= 0 {
-                    self.resize = Resize::None;
-                    return;
-                }
-                (width0, height0 as u16, width1, height1 as u16)
-            }
-        };
-        // Use delta to determine which component to size first, none if 0
-        let (index0, width0, height0, index1, width1, height1) = match delta.cmp(&0) {
-            Ordering::Less => (index0, width0, height0, index1, width1, height1),
-            Ordering::Greater => (index1, width1, height1, index0, width0, height0),
-            Ordering::Equal => return,
-        };
-        // Resize component that is getting smaller first, there should be
-        // no issue resizing component that is getting bigger
-        if self.get_children_mut()[index0]
-            .as_base_mut()
-            .resize(width0, height0)
-            .is_err()
-        {
-            self.resize = Resize::None;
+        let delta = mouse_offset_next as i16 - mouse_offset as i16;
+        if delta == 0 {
             return;
         }
-        assert_eq!(
-            self.get_children_mut()[index1]
-                .as_base_mut()
-                .resize(width1, height1),
-            Ok(())
-        );
-        // Update resize event to current offset
+        // Move the divider as far towards the mouse as the sizes allow, which
+        // leaves fixed size components alone unless they are adjustable
+        let Some(moved) = self.move_divider(divider, delta) else {
+            self.resize = Resize::None;
+            return;
+        };
+        // Update resize event to where the divider is now
+        let mouse_offset = (mouse_offset as i16 + moved) as u16;
         self.resize = match self.resize {
-            Resize::LeftTop {
-                mouse_offset: _,
-                child_index,
-            } => Resize::LeftTop {
-                mouse_offset: mouse_offset_next,
+            Resize::LeftTop { child_index, .. } => Resize::LeftTop {
+                mouse_offset,
                 child_index,
             },
-            Resize::RightBottom {
-                mouse_offset: _,
-                child_index,
-            } => Resize::RightBottom {
-                mouse_offset: mouse_offset_next,
+            Resize::RightBottom { child_index, .. } => Resize::RightBottom {
+                mouse_offset,
                 child_index,
             },
             Resize::None => Resize::None,
         };
     }
+
+    /// Moves the divider after the child at the index by the delta, growing
+    /// one of the children on either side and shrinking the other, limited by
+    /// the size ranges of both, returning how far the divider moved or none if
+    /// the children could not be resized
+    fn move_divider(&mut self, index: usize, delta: i16) -> Option<i16> {
+        if index + 1 >= self.children.len() {
+            return None;
+        }
+        let orientation = self.orientation.clone();
+        let size = |c: &ContainerChild| match orientation {
+            Direction::Horizontal => c.as_base().get_width() as i32,
+            Direction::Vertical => c.as_base().get_height() as i32,
+        };
+        let (size0, size1) = (size(&self.children[index]), size(&self.children[index + 1]));
+        let (min0, max0) = self.children[index]
+            .as_base()
+            .get_size_range(orientation.clone());
+        let (min1, max1) = self.children[index + 1]
+            .as_base()
+            .get_size_range(orientation.clone());
+        // Limit the delta to what both children allow
+        let lower = std::cmp::max(min0 as i32 - size0, size1 - max1 as i32);
+        let upper = std::cmp::min(max0 as i32 - size0, size1 - min1 as i32);
+        if lower > upper {
+            return None;
+        }
+        let delta = (delta as i32).clamp(lower, upper);
+        if delta == 0 {
+            return Some(0);
+        }
+        // Resize the child getting smaller first, there should be no issue
+        // resizing the child getting bigger
+        let (shrink, shrink_size, grow, grow_size) = if delta < 0 {
+            (index, size0 + delta, index + 1, size1 - delta)
+        } else {
+            (index + 1, size1 - delta, index, size0 + delta)
+        };
+        self.resize_child_along(shrink, shrink_size as u16).ok()?;
+        if self.resize_child_along(grow, grow_size as u16).is_err() {
+            let _ = self.resize_child_along(shrink, if delta < 0 { size0 } else { size1 } as u16);
+            return None;
+        }
+        Some(delta as i16)
+    }
+
+    /// Resizes the child at the index to the size along the orientation,
+    /// changing the fixed size of an adjustable component
+    fn resize_child_along(&mut self, index: usize, size: u16) -> Result<(), ResizeError> {
+        let (width, height) = match self.orientation {
+            Direction::Horizontal => (size, self.height),
+            Direction::Vertical => (self.width, size),
+        };
+        if let ContainerChild::Component(component) = &mut self.children[index] {
+            match self.orientation {
+                Direction::Horizontal
+                    if component.is_fixed_width() && component.is_adjustable() =>
+                {
+                    component.set_fixed_width(Some(size))
+                }
+                Direction::Vertical if component.is_fixed_height() && component.is_adjustable() => {
+                    component.set_fixed_height(Some(size))
+                }
+                _ => {}
+            }
+        }
+        let child = self.children[index].as_base_mut();
+        child.resize(width, height)?;
+        child.invalidate();
+        Ok(())
+    }
+
+    fn handle_ignored_key(&mut self, event: KeyEvent) -> EventResult {
+        match &mut self.key_handler {
+            Some(key_handler) => key_handler(event),
+            None => EventResult::Ignored,
+        }
+    }
 }
 
 impl Container for ContainerList {
@@ -375,9 +862,11 @@ impl Container for ContainerList {
                 width: child.as_base().get_width(),
                 height: child.as_base().get_height(),
             });
+            // Leave a gap for the splitter after each child
+            let gap = self.splitter.map_or(0, |_| 1);
             match self.orientation {
-                Direction::Horizontal => pos.x += child.as_base().get_width(),
-                Direction::Vertical => pos.y += child.as_base().get_height(),
+                Direction::Horizontal => pos.x += child.as_base().get_width() + gap,
+                Direction::Vertical => pos.y += child.as_base().get_height() + gap,
             }
         }
         rects
@@ -395,6 +884,112 @@ impl Container for ContainerList {
         self.resizable
     }
 
+    fn refresh(&mut self) -> Result<(), ResizeError> {
+        // Nothing to lay out until the container has been sized
+        if self.width == 0 && self.height == 0 {
+            return Ok(());
+        }
+        self.resize_children(self.width, self.height)
+    }
+
+    fn get_menu_items(&self) -> Vec<MenuItem> {
+        self.menu_items.clone()
+    }
+
+    fn resize_child(&mut self, name: &str, border: Border, delta: i16) -> bool {
+        if !self.resizable {
+            return false;
+        }
+        let Some(index) = self
+            .children
+            .iter()
+            .position(|c| c.as_base().get_name() == name)
+        else {
+            return false;
+        };
+        // Moving the divider before the child towards the start grows it
+        let moved = match (&self.orientation, border) {
+            (Direction::Horizontal, Border::Left) | (Direction::Vertical, Border::Top) => {
+                index > 0 && self.move_divider(index - 1, -delta).is_some_and(|d| d != 0)
+            }
+            (Direction::Horizontal, Border::Right) | (Direction::Vertical, Border::Bottom) => {
+                self.move_divider(index, delta).is_some_and(|d| d != 0)
+            }
+            _ => false,
+        };
+        if moved {
+            self.invalidate();
+        }
+        moved
+    }
+
+    fn equalize(&mut self, recursive: bool) -> Result<(), ResizeError> {
+        let sizes = self.get_sizes_along();
+        self.refresh_with_ratios(vec![1.0; self.children.len()])?;
+        if !recursive {
+            return Ok(());
+        }
+        for i in 0..self.children.len() {
+            let ContainerChild::Container(container) = &mut self.children[i] else {
+                continue;
+            };
+            if let Err(err) = container.equalize(true) {
+                let _ = self.refresh_with_ratios(sizes);
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    fn rotate(&mut self, reverse: bool) -> Result<(), ResizeError> {
+        // Each position keeps its size for the child moved into it
+        let sizes = self.get_sizes_along();
+        let rotate = |children: &mut Vec<ContainerChild>, reverse: bool| match reverse {
+            true => children.rotate_left(1),
+            false => children.rotate_right(1),
+        };
+        rotate(&mut self.children, reverse);
+        if let Err(err) = self.refresh_with_ratios(sizes) {
+            rotate(&mut self.children, !reverse);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn flip(&mut self) -> Result<(), ResizeError> {
+        // Keep the proportions of the children along the new orientation
+        let sizes = self.get_sizes_along();
+        let orientation = self.orientation.clone();
+        self.orientation = match orientation {
+            Direction::Horizontal => Direction::Vertical,
+            Direction::Vertical => Direction::Horizontal,
+        };
+        if let Err(err) = self.refresh_with_ratios(sizes) {
+            self.orientation = orientation;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// The only other arrangement of a list is the other orientation
+    fn next_layout(&mut self) -> Result<(), ResizeError> {
+        self.flip()
+    }
+
+    fn remove_child(&mut self, name: &str) -> Option<ContainerChild> {
+        let index = self
+            .children
+            .iter()
+            .position(|c| c.as_base().get_name() == name)?;
+        let child = self.children.remove(index);
+        if self.refresh().is_err() {
+            self.children.insert(index, child);
+            let _ = self.refresh();
+            return None;
+        }
+        Some(child)
+    }
+
     fn as_container(&self) -> &dyn Container {
         self
     }
@@ -409,15 +1004,22 @@ impl ComponentBase for ContainerList {
         // Check if the mouse event is none
         let Some(kind) = kind else {
             // Issue none to all children
-            for child in &mut self.children {
-                child.as_base_mut().handle_mouse(0, 0, None);
-            }
-            // Clear current resizing
+            for_each_child(&mut self.children, |c| c.handle_mouse(0, 0, None));
+            // Clear current resizing and capture
             self.resize = Resize::None;
+            self.capture = None;
+            self.splitter_hover = None;
             return;
         };
-        // Ignore mouse move events (TODO: Consider reworking this)
+        // Send mouse move events to the child under the mouse, letting the rest
+        // know the mouse left them without changing focus
         if let MouseEventKind::Moved = kind {
+            self.splitter_hover = self.get_splitter_index(x, y);
+            hover_child(self, x, y);
+            return;
+        }
+        // Scroll events go to handle_scroll instead, which has the modifiers
+        if let MouseEventKind::ScrollDown | MouseEventKind::ScrollUp = kind {
             return;
         }
         // Handle an ongoing resize event
@@ -425,9 +1027,34 @@ impl ComponentBase for ContainerList {
             Direction::Horizontal => x,
             Direction::Vertical => y,
         };
-        self.handle_resize(mouse_offset, self.get_orientation(), kind);
+        self.handle_resize(mouse_offset, kind);
         let mouse_pos = ComponentPos { x, y };
         let child_rects = self.as_container().get_children_rectangles();
+        // Send drags and the release to the child the drag started in
+        if let (Some(index), MouseEventKind::Drag(_) | MouseEventKind::Up(_)) = (self.capture, kind)
+        {
+            if let MouseEventKind::Up(_) = kind {
+                self.capture = None;
+            }
+            handle_captured_child(self, index, x as i32, y as i32, kind);
+            return;
+        }
+        if let MouseEventKind::Down(_) = kind {
+            self.capture = child_rects
+                .iter()
+                .position(|r| mouse_pos.intersects_rect(*r));
+        }
+        // Splitters are drag handles for the divider after the child
+        let splitter = self.get_splitter_index(x, y);
+        if let (MouseEventKind::Down(MouseButton::Left), Some(index)) = (kind, splitter) {
+            self.resize = Resize::new(mouse_offset, Border::Right, index, child_rects.len());
+        }
+        // Splitters and drags started on them belong to no child, so leave the
+        // children and their focus alone
+        let dragging = matches!(kind, MouseEventKind::Drag(_) | MouseEventKind::Up(_));
+        if splitter.is_some() || (dragging && self.capture.is_none()) {
+            return;
+        }
         // Iterate through children, dispatching mouse event if intersects
         for (i, child) in self.children.iter_mut().enumerate() {
             // Check mouse intersection, issue none if no intersection
@@ -436,9 +1063,14 @@ impl ComponentBase for ContainerList {
                 continue;
             }
             let (child_x, child_y) = (x - child_rects[i].x, y - child_rects[i].y);
-            // Check if mouse intersects a child border
-            if let Some(border) = child.as_base().get_border(child_x, child_y) {
-                if let MouseEventKind::Down(MouseButton::Left) = kind {
+            // Check if mouse intersects a child border along the orientation,
+            // which may be the outer border of a child nested at any depth
+            if let MouseEventKind::Down(MouseButton::Left) = kind {
+                let border =
+                    child
+                        .as_base()
+                        .get_border_along(child_x, child_y, self.orientation.clone());
+                if let Some(border) = border {
                     self.resize = Resize::new(mouse_offset, border, i, child_rects.len());
                 }
             }
@@ -449,140 +1081,82 @@ impl ComponentBase for ContainerList {
         }
     }
 
-    fn handle_key(&mut self, event: KeyEvent) -> Option<Border> {
-        let (container_width, container_height) = (self.get_width(), self.get_height());
-        // Send key event to the (partially) focused component
-        match self.as_container_mut().search_focused_mut() {
-            FocusResult::Focus((component, pos)) | FocusResult::PartialFocus((component, pos)) => {
-                // Process key and see if focus needs to change
-                let Some(border) = component.handle_key(event) else {
-                    return None;
-                };
-                // Look for position of next component to focus
-                let next_pos = find_next_pos(
-                    pos,
-                    border,
-                    component.get_width(),
-                    component.get_height(),
-                    container_width,
-                    container_height,
-                );
-                // Check the position actually exists
-                let Some(next_pos) = next_pos else {
-                    component.set_focus(Focus::PartialFocus);
-                    return None;
-                };
-                // Partially focus component
-                if let Some((component_next, _)) =
-                    self.as_container_mut().search_position_mut(next_pos)
-                {
-                    component_next.set_focus(Focus::PartialFocus);
-                }
-            }
-            FocusResult::None => {
-                // If nothing has focus, check if the right keys were pressed
-                match event.code {
-                    KeyCode::Enter
-                    | KeyCode::Up
-                    | KeyCode::Down
-                    | KeyCode::Left
-                    | KeyCode::Right => {}
-                    _ => return None,
-                }
-                // Find something to focus
-                if let Some((component, _)) = self
-                    .as_container_mut()
-                    .search_position_mut(ComponentPos { x: 0, y: 0 })
-                {
-                    component.set_focus(Focus::PartialFocus);
-                }
-            }
+    fn handle_mouse_leave(&mut self) {
+        self.splitter_hover = None;
+        for child in &mut self.children {
+            child.as_base_mut().handle_mouse_leave();
         }
-        None
     }
 
-    fn handle_update(&mut self) {
-        for component in &mut self.children {
-            component.as_base_mut().handle_update();
+    fn handle_mouse_captured(&mut self, x: i32, y: i32, kind: MouseEventKind) {
+        let Some(index) = self.capture else {
+            return;
+        };
+        // Keep resizing while dragging outside of the container
+        let mouse_offset = match self.orientation {
+            Direction::Horizontal => x,
+            Direction::Vertical => y,
+        };
+        let mouse_offset = mouse_offset.clamp(0, u16::MAX as i32) as u16;
+        self.handle_resize(mouse_offset, kind);
+        if let MouseEventKind::Up(_) = kind {
+            self.capture = None;
         }
+        handle_captured_child(self, index, x, y, kind);
     }
 
-    fn invalidate(&mut self) {
-        for component in &mut self.children {
-            component.as_base_mut().invalidate();
+    fn handle_click(&mut self, x: u16, y: u16, button: MouseButton, count: u8) {
+        click_child(self, x, y, button, count);
+    }
+
+    fn handle_scroll(&mut self, x: u16, y: u16, kind: MouseEventKind, modifiers: KeyModifiers) {
+        scroll_child(self, x, y, kind, modifiers);
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> EventResult {
+        // Send key event to the child with (partial) focus, or find something
+        // to focus if nothing has focus
+        let Some(index) = get_focused_index(&self.children) else {
+            return match focus_focusable(self, event) {
+                EventResult::Ignored => self.handle_ignored_key(event),
+                result => result,
+            };
+        };
+        match self.children[index].as_base_mut().handle_key(event) {
+            EventResult::Border(border) => move_focus(self, border),
+            EventResult::Ignored => self.handle_ignored_key(event),
+            result => result,
         }
     }
 
+    fn handle_paste(&mut self, text: &str) -> EventResult {
+        paste_focused(&mut self.children, text)
+    }
+
+    fn handle_terminal_focus(&mut self, focused: bool) {
+        for_each_child(&mut self.children, |c| c.handle_terminal_focus(focused));
+    }
+
+    fn handle_update(&mut self) {
+        for_each_child(&mut self.children, |c| c.handle_update());
+    }
+
+    fn invalidate(&mut self) {
+        for_each_child(&mut self.children, |c| c.invalidate());
+    }
+
     fn resize(&mut self, width: u16, height: u16) -> Result<(), ResizeError> {
         if self.width == width && self.height == height {
             return Ok(());
         }
-        // Get constraints for resizing later
-        let Some(constraints) = self.get_sizing_constraints() else {
-            return Err(ResizeError {
-                name: self.get_name(),
-                width,
-                height,
-                border_width: 0
-            });
-        };
-        // Get current sizing
-        let old_dimensions = self
-            .children
-            .iter()
-            .map(|c| (c.as_base().get_width(), c.as_base().get_height()))
-            .collect::<Vec<(u16, u16)>>();
-        // Calculate new sizing
-        let Some(new_sizes) = ContainerList::calculate_sizes(
-            constraints,
-            match self.orientation {
-                Direction::Horizontal => width,
-                Direction::Vertical => height,
-            },
-        ) else {
-            return Err(ResizeError {
-                name: self.get_name(),
-                width,
-                height,
-                border_width: 0
-            });
-        };
-        let new_sizes = new_sizes
-            .iter()
-            .map(|s| match self.orientation {
-                Direction::Horizontal => (*s, height),
-                Direction::Vertical => (width, *s),
-            })
-            .collect::<Vec<(u16, u16)>>();
-        for (i, size) in new_sizes.iter().enumerate().take(self.children.len()) {
-            if let Err(err) = self.children[i].as_base_mut().resize(size.0, size.1) {
-                for (i, dim) in old_dimensions.iter().enumerate().take(self.children.len()) {
-                    let _ = self.children[i].as_base_mut().resize(dim.0, dim.1);
-                }
-                return Err(err);
-            }
-        }
-        self.width = width;
-        self.height = height;
-        self.invalidate();
-        Ok(())
+        self.resize_children(width, height)
     }
 
     fn render(&mut self, area: Rect, buf: &mut Buffer) {
         assert_eq!(area.width, self.width);
         assert_eq!(area.height, self.height);
-        let child_rects = self.as_container().get_children_rectangles();
-        for (i, child) in self.children.iter_mut().enumerate() {
-            child.as_base_mut().render(
-                Rect {
-                    x: child_rects[i].x + area.x,
-                    y: child_rects[i].y + area.y,
-                    height: child_rects[i].height,
-                    width: child_rects[i].width,
-                },
-                buf,
-            );
-        }
+        render_children(self, area, buf);
+        self.render_splitters(area, buf);
     }
 
     fn get_width(&self) -> u16 {
@@ -601,21 +1175,40 @@ impl ComponentBase for ContainerList {
         self.children.iter().all(|c| c.as_base().is_fixed_height())
     }
 
-    fn get_focus(&self) -> Focus {
-        for component in &self.children {
-            match component.as_base().get_focus() {
-                Focus::Focus => return Focus::Focus,
-                Focus::PartialFocus => return Focus::PartialFocus,
-                _ => {}
-            }
+    fn get_size_range(&self, direction: Direction) -> (u16, u16) {
+        let ranges = self
+            .children
+            .iter()
+            .map(|c| c.as_base().get_size_range(direction.clone()));
+        // Children add up along the orientation and share the size across it
+        if direction == self.orientation {
+            let splitters = self.get_splitters_size();
+            ranges.fold(
+                (splitters, splitters),
+                |(min, max), (child_min, child_max)| {
+                    (min.saturating_add(child_min), max.saturating_add(child_max))
+                },
+            )
+        } else {
+            ranges.fold((0, u16::MAX), |(min, max), (child_min, child_max)| {
+                (std::cmp::max(min, child_min), std::cmp::min(max, child_max))
+            })
         }
-        Focus::None
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.children.iter().any(|c| c.as_base().is_focusable())
+    }
+
+    fn get_focus(&self) -> Focus {
+        get_children_focus(&self.children)
     }
 
     fn get_name(&self) -> String {
         self.name.clone()
     }
 
+    #[allow(clippy::question_mark)]
     fn get_border(&self, x: u16, y: u16) -> Option<Border> {
         let pos = ComponentPos { x, y };
         let child_rects = self.as_container().get_children_rectangles();
@@ -648,4 +1241,25 @@ impl ComponentBase for ContainerList {
         }
         None
     }
+
+    fn get_border_along(&self, x: u16, y: u16, direction: Direction) -> Option<Border> {
+        let pos = ComponentPos { x, y };
+        let child_rects = self.as_container().get_children_rectangles();
+        let index = child_rects.iter().position(|r| pos.intersects_rect(*r))?;
+        let border = self.children[index].as_base().get_border_along(
+            x - child_rects[index].x,
+            y - child_rects[index].y,
+            direction.clone(),
+        )?;
+        // Borders across the orientation are shared by all children, while
+        // borders between children belong to this container
+        if direction != self.orientation {
+            return Some(border);
+        }
+        match border {
+            Border::Left | Border::Top if index == 0 => Some(border),
+            Border::Right | Border::Bottom if index == self.children.len() - 1 => Some(border),
+            _ => None,
+        }
+    }
 }
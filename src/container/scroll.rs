@@ -0,0 +1,749 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+use tui::{
+    buffer::Buffer,
+    layout::{Direction, Rect},
+    style::{Color, Style},
+};
+
+use crate::{
+    container::list::*, container::search::ContainerSearch, container::*, pos::*, Border,
+    EventResult, Focus, MenuItem, ResizeError,
+};
+
+/// Sizes of the scrolled content and the window showing it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Viewport {
+    content_width: u16,
+    content_height: u16,
+    view_width: u16,
+    view_height: u16,
+    vertical_bar: bool,
+    horizontal_bar: bool,
+}
+
+/// Returns the start and length of the scrollbar thumb along a bar of the
+/// view length
+fn get_thumb(view: u16, content: u16, offset: u16) -> (u16, u16) {
+    let (view, content, offset) = (view as u32, content as u32, offset as u32);
+    let length = std::cmp::max(view * view / std::cmp::max(content, 1), 1).min(view);
+    let max_offset = content.saturating_sub(view);
+    let start = match max_offset {
+        0 => 0,
+        _ => offset * (view - length) / max_offset,
+    };
+    (start as u16, length as u16)
+}
+
+/// Returns the offset that puts the scrollbar thumb at the position along a
+/// bar of the view length
+fn get_thumb_offset(view: u16, content: u16, pos: u16) -> u16 {
+    let max_offset = content.saturating_sub(view) as u32;
+    match view {
+        0 | 1 => 0,
+        _ => (pos.min(view - 1) as u32 * max_offset / (view as u32 - 1)) as u16,
+    }
+}
+
+/// Container showing a scrolled window of a single child, which is given the
+/// virtual size or its smallest size if either is larger than the container
+///
+/// The child is laid out in the coordinates of the whole content, which is
+/// drawn offscreen and so can be at most 65535 cells, while the positions
+/// found by searching through this container are those shown in the window
+pub struct ContainerScroll {
+    name: String,
+    virtual_width: Option<u16>,
+    virtual_height: Option<u16>,
+    offset_x: u16,
+    offset_y: u16,
+    scroll_step: u16,
+    scrollbars: bool,
+    viewport: Viewport,
+    capture: bool,
+    scrollbar_drag: Option<Direction>,
+    focused_name: Option<String>,
+    width: u16,
+    height: u16,
+    children: Vec<ContainerChild>,
+    content: Buffer,
+}
+
+impl ContainerScroll {
+    pub fn new(name: String, width: u16, height: u16) -> Self {
+        Self {
+            name,
+            virtual_width: None,
+            virtual_height: None,
+            offset_x: 0,
+            offset_y: 0,
+            scroll_step: 3,
+            scrollbars: true,
+            viewport: Viewport::default(),
+            capture: false,
+            scrollbar_drag: None,
+            focused_name: None,
+            width,
+            height,
+            children: Vec::new(),
+            content: Buffer::empty(Rect::default()),
+        }
+    }
+
+    /// Sets the child shown in the container, replacing any previous child,
+    /// and returns an error if the content for the child would be larger than
+    /// 65535 cells
+    pub fn from_child(mut self, child: ContainerChild) -> Result<Self, ResizeError> {
+        self.children = vec![child];
+        self.refresh()?;
+        Ok(self)
+    }
+
+    pub fn get_virtual_size(&self) -> (Option<u16>, Option<u16>) {
+        (self.virtual_width, self.virtual_height)
+    }
+
+    /// Sets the smallest size of the content along each direction, keeping the
+    /// previous size if the child cannot be resized to it or the content would
+    /// be larger than 65535 cells
+    pub fn set_virtual_size(
+        &mut self,
+        width: Option<u16>,
+        height: Option<u16>,
+    ) -> Result<(), ResizeError> {
+        let previous = (self.virtual_width, self.virtual_height);
+        (self.virtual_width, self.virtual_height) = (width, height);
+        if let Err(err) = self.refresh() {
+            (self.virtual_width, self.virtual_height) = previous;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    pub fn virtual_size(
+        mut self,
+        width: Option<u16>,
+        height: Option<u16>,
+    ) -> Result<Self, ResizeError> {
+        self.set_virtual_size(width, height)?;
+        Ok(self)
+    }
+
+    pub fn get_scrollbars(&self) -> bool {
+        self.scrollbars
+    }
+
+    /// Sets whether scrollbars are drawn along the right and bottom of the
+    /// container, taking a column and row from the content when needed
+    pub fn set_scrollbars(&mut self, scrollbars: bool) -> Result<(), ResizeError> {
+        let previous = self.scrollbars;
+        self.scrollbars = scrollbars;
+        if let Err(err) = self.refresh() {
+            self.scrollbars = previous;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    pub fn scrollbars(mut self, scrollbars: bool) -> Result<Self, ResizeError> {
+        self.set_scrollbars(scrollbars)?;
+        Ok(self)
+    }
+
+    pub fn get_scroll_step(&self) -> u16 {
+        self.scroll_step
+    }
+
+    /// Sets how many cells the content moves for each step of the mouse wheel
+    pub fn set_scroll_step(&mut self, scroll_step: u16) {
+        self.scroll_step = scroll_step;
+    }
+
+    pub fn scroll_step(mut self, scroll_step: u16) -> Self {
+        self.set_scroll_step(scroll_step);
+        self
+    }
+
+    /// Returns the size of the content the child is laid out in
+    pub fn get_content_size(&self) -> (u16, u16) {
+        (self.viewport.content_width, self.viewport.content_height)
+    }
+
+    /// Returns the position in the content shown at the top left corner
+    pub fn get_offset(&self) -> (u16, u16) {
+        (self.offset_x, self.offset_y)
+    }
+
+    /// Scrolls the content so the position is shown at the top left corner,
+    /// stopping at the ends, and returns false if the offset did not change
+    pub fn scroll_to(&mut self, x: u16, y: u16) -> bool {
+        let max_x = self
+            .viewport
+            .content_width
+            .saturating_sub(self.viewport.view_width);
+        let max_y = self
+            .viewport
+            .content_height
+            .saturating_sub(self.viewport.view_height);
+        let previous = self.get_offset();
+        self.offset_x = x.min(max_x);
+        self.offset_y = y.min(max_y);
+        self.get_offset() != previous
+    }
+
+    /// Scrolls the content by the delta, stopping at the ends, and returns
+    /// false if the offset did not change
+    pub fn scroll_by(&mut self, dx: i32, dy: i32) -> bool {
+        let x = (self.offset_x as i32 + dx).clamp(0, u16::MAX as i32);
+        let y = (self.offset_y as i32 + dy).clamp(0, u16::MAX as i32);
+        self.scroll_to(x as u16, y as u16)
+    }
+
+    /// Scrolls the least needed to show the focused component, preferring its
+    /// top left corner if it does not fit, and returns false if the offset
+    /// did not change
+    pub fn scroll_to_focused(&mut self) -> bool {
+        // Search the child itself for the position in the content
+        let focused = match self.children.first() {
+            Some(ContainerChild::Container(container)) => {
+                get_focused_position(container.as_container())
+            }
+            Some(ContainerChild::Component(component)) if component.get_focus() != Focus::None => {
+                Some(((0, 0), component.get_width(), component.get_height()))
+            }
+            _ => None,
+        };
+        let Some(((x, y), width, height)) = focused else {
+            return false;
+        };
+        let pos = ComponentPos {
+            x: x.max(0) as u16,
+            y: y.max(0) as u16,
+        };
+        let fit = |offset: u16, start: u16, size: u16, view: u16| {
+            if start < offset || size > view {
+                start
+            } else if start + size > offset + view {
+                start + size - view
+            } else {
+                offset
+            }
+        };
+        let x = fit(self.offset_x, pos.x, width, self.viewport.view_width);
+        let y = fit(self.offset_y, pos.y, height, self.viewport.view_height);
+        self.scroll_to(x, y)
+    }
+
+    /// Scrolls to the focused component if focus moved to another component
+    /// since the last time, whatever moved it
+    fn follow_focus(&mut self) {
+        let focused_name = self.as_container().search_focused_name();
+        if focused_name != self.focused_name {
+            self.focused_name = focused_name;
+            self.scroll_to_focused();
+        }
+    }
+
+    /// Calculates the sizes of the content and the window for the size of the
+    /// container, adding a scrollbar along each direction the content is
+    /// larger than the window
+    fn get_viewport(&self, width: u16, height: u16) -> Viewport {
+        let range = |direction: Direction| match self.children.first() {
+            Some(child) => child.as_base().get_size_range(direction),
+            None => (0, u16::MAX),
+        };
+        let (min_width, max_width) = range(Direction::Horizontal);
+        let (min_height, max_height) = range(Direction::Vertical);
+        let wanted_width = std::cmp::max(min_width, self.virtual_width.unwrap_or(0));
+        let wanted_height = std::cmp::max(min_height, self.virtual_height.unwrap_or(0));
+        let gutter = self.scrollbars as u16;
+        let (mut vertical_bar, mut horizontal_bar) = (false, false);
+        // Each scrollbar makes the window smaller, which can require the other
+        for _ in 0..3 {
+            let view_width = width.saturating_sub(gutter * vertical_bar as u16);
+            let view_height = height.saturating_sub(gutter * horizontal_bar as u16);
+            vertical_bar = wanted_height > view_height;
+            horizontal_bar = wanted_width > view_width;
+        }
+        let view_width = width.saturating_sub(gutter * vertical_bar as u16);
+        let view_height = height.saturating_sub(gutter * horizontal_bar as u16);
+        Viewport {
+            content_width: std::cmp::max(wanted_width, view_width.min(max_width)),
+            content_height: std::cmp::max(wanted_height, view_height.min(max_height)),
+            view_width,
+            view_height,
+            vertical_bar,
+            horizontal_bar,
+        }
+    }
+
+    /// Resizes the child to the content for the new size, rolling back to the
+    /// previous size if the child fails to resize
+    fn resize_children(&mut self, width: u16, height: u16) -> Result<(), ResizeError> {
+        let viewport = self.get_viewport(width, height);
+        let (content_width, content_height) = (viewport.content_width, viewport.content_height);
+        // The content is drawn into a buffer, which can only hold so many cells
+        if content_width as u32 * content_height as u32 > u16::MAX as u32 {
+            return Err(ResizeError {
+                name: self.name.clone(),
+                width: content_width,
+                height: content_height,
+                border_width: 0,
+            });
+        }
+        if let Some(child) = self.children.first_mut() {
+            let child = child.as_base_mut();
+            let (old_width, old_height) = (child.get_width(), child.get_height());
+            if let Err(err) = child.resize(content_width, content_height) {
+                let _ = child.resize(old_width, old_height);
+                return Err(err);
+            }
+        }
+        if (content_width, content_height) != self.get_content_size() {
+            self.content = Buffer::empty(Rect {
+                x: 0,
+                y: 0,
+                width: content_width,
+                height: content_height,
+            });
+        }
+        self.width = width;
+        self.height = height;
+        self.viewport = viewport;
+        self.capture = false;
+        self.scrollbar_drag = None;
+        self.scroll_by(0, 0);
+        self.invalidate();
+        Ok(())
+    }
+
+    /// Returns the direction of the scrollbar at the position
+    fn get_scrollbar(&self, x: u16, y: u16) -> Option<Direction> {
+        if !self.scrollbars {
+            return None;
+        }
+        let viewport = &self.viewport;
+        if viewport.vertical_bar && x == viewport.view_width && y < viewport.view_height {
+            return Some(Direction::Vertical);
+        }
+        if viewport.horizontal_bar && y == viewport.view_height && x < viewport.view_width {
+            return Some(Direction::Horizontal);
+        }
+        None
+    }
+
+    /// Scrolls so the thumb of the scrollbar is at the position
+    fn handle_scrollbar(&mut self, direction: Direction, x: i32, y: i32) {
+        let viewport = &self.viewport;
+        match direction {
+            Direction::Vertical => {
+                let pos = y.clamp(0, u16::MAX as i32) as u16;
+                let offset_y = get_thumb_offset(viewport.view_height, viewport.content_height, pos);
+                self.scroll_to(self.offset_x, offset_y);
+            }
+            Direction::Horizontal => {
+                let pos = x.clamp(0, u16::MAX as i32) as u16;
+                let offset_x = get_thumb_offset(viewport.view_width, viewport.content_width, pos);
+                self.scroll_to(offset_x, self.offset_y);
+            }
+        }
+    }
+
+    /// Returns true if the position is in the window showing the content
+    fn in_view(&self, x: u16, y: u16) -> bool {
+        x < self.viewport.view_width.min(self.viewport.content_width)
+            && y < self.viewport.view_height.min(self.viewport.content_height)
+    }
+
+    /// Returns the border of the child if it is shown on the outside of this
+    /// container
+    fn get_outer_border(&self, x: u16, y: u16, border: Border) -> Option<Border> {
+        let outer = match border {
+            Border::Left => x == 0,
+            Border::Top => y == 0,
+            Border::Right => x + 1 == self.width,
+            Border::Bottom => y + 1 == self.height,
+        };
+        outer.then_some(border)
+    }
+
+    /// Draws the scrollbars with the thumb showing which part of the content
+    /// is in the window
+    fn render_scrollbars(&self, area: Rect, buf: &mut Buffer) {
+        let viewport = &self.viewport;
+        let track = Style::default().fg(Color::DarkGray);
+        let thumb = Style::default().fg(Color::White);
+        if viewport.vertical_bar {
+            let (start, length) =
+                get_thumb(viewport.view_height, viewport.content_height, self.offset_y);
+            for y in 0..viewport.view_height {
+                let (symbol, style) = match y >= start && y < start + length {
+                    true => ("█", thumb),
+                    false => ("│", track),
+                };
+                buf.get_mut(area.x + viewport.view_width, area.y + y)
+                    .set_symbol(symbol)
+                    .set_style(style);
+            }
+        }
+        if viewport.horizontal_bar {
+            let (start, length) =
+                get_thumb(viewport.view_width, viewport.content_width, self.offset_x);
+            for x in 0..viewport.view_width {
+                let (symbol, style) = match x >= start && x < start + length {
+                    true => ("█", thumb),
+                    false => ("─", track),
+                };
+                buf.get_mut(area.x + x, area.y + viewport.view_height)
+                    .set_symbol(symbol)
+                    .set_style(style);
+            }
+        }
+        if viewport.vertical_bar && viewport.horizontal_bar {
+            buf.get_mut(area.x + viewport.view_width, area.y + viewport.view_height)
+                .reset();
+        }
+    }
+}
+
+impl Container for ContainerScroll {
+    fn get_children(&self) -> &Vec<ContainerChild> {
+        &self.children
+    }
+
+    fn get_children_mut(&mut self) -> &mut Vec<ContainerChild> {
+        &mut self.children
+    }
+
+    /// The child is shown in the window, or in the part of it the content
+    /// covers if the content is smaller
+    fn get_children_rectangles(&self) -> Vec<Rect> {
+        let viewport = &self.viewport;
+        self.children
+            .iter()
+            .map(|_| Rect {
+                x: 0,
+                y: 0,
+                width: viewport.view_width.min(viewport.content_width),
+                height: viewport.view_height.min(viewport.content_height),
+            })
+            .collect()
+    }
+
+    /// The window shows the content from the scroll offset
+    fn get_child_view(&self, _index: usize) -> Option<Rect> {
+        let rect = self.get_children_rectangles().first().cloned()?;
+        Some(Rect {
+            x: self.offset_x,
+            y: self.offset_y,
+            ..rect
+        })
+    }
+
+    fn as_base(&self) -> &dyn ComponentBase {
+        self
+    }
+
+    fn as_base_mut(&mut self) -> &mut dyn ComponentBase {
+        self
+    }
+
+    fn is_resizable(&self) -> bool {
+        false
+    }
+
+    fn refresh(&mut self) -> Result<(), ResizeError> {
+        // Nothing to lay out until the container has been sized
+        if self.width == 0 && self.height == 0 {
+            return Ok(());
+        }
+        self.resize_children(self.width, self.height)
+    }
+
+    /// The items of the container shown, as this container adds none
+    fn get_menu_items(&self) -> Vec<MenuItem> {
+        match self.children.first() {
+            Some(ContainerChild::Container(container)) => container.get_menu_items(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn equalize(&mut self, recursive: bool) -> Result<(), ResizeError> {
+        match self.children.first_mut() {
+            Some(ContainerChild::Container(container)) if recursive => container.equalize(true),
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes the child itself, leaving the container empty, or otherwise
+    /// removes the child from the container shown and lays out the content
+    /// again for what is left
+    fn remove_child(&mut self, name: &str) -> Option<ContainerChild> {
+        let child = self.children.first_mut()?;
+        if child.as_base().get_name() == name {
+            self.focused_name = None;
+            return self.children.pop();
+        }
+        let ContainerChild::Container(container) = child else {
+            return None;
+        };
+        let removed = container.remove_child(name)?;
+        let _ = self.refresh();
+        Some(removed)
+    }
+
+    fn as_container(&self) -> &dyn Container {
+        self
+    }
+
+    fn as_container_mut(&mut self) -> &mut dyn Container {
+        self
+    }
+}
+
+impl ComponentBase for ContainerScroll {
+    fn handle_mouse(&mut self, x: u16, y: u16, kind: Option<MouseEventKind>) {
+        // Check if the mouse event is none
+        let Some(kind) = kind else {
+            if let Some(child) = self.children.first_mut() {
+                child.as_base_mut().handle_mouse(0, 0, None);
+            }
+            self.capture = false;
+            self.scrollbar_drag = None;
+            return;
+        };
+        // Scroll events go to handle_scroll instead, which has the modifiers
+        if let MouseEventKind::ScrollDown | MouseEventKind::ScrollUp = kind {
+            return;
+        }
+        // Drags and the release go to whatever the drag started on
+        if let MouseEventKind::Drag(_) | MouseEventKind::Up(_) = kind {
+            if self.scrollbar_drag.is_some() || self.capture {
+                self.handle_mouse_captured(x as i32, y as i32, kind);
+                return;
+            }
+        }
+        if let (Some(direction), MouseEventKind::Down(MouseButton::Left)) =
+            (self.get_scrollbar(x, y), kind)
+        {
+            self.handle_scrollbar(direction.clone(), x as i32, y as i32);
+            self.scrollbar_drag = Some(direction);
+            return;
+        }
+        let in_view = self.in_view(x, y);
+        let Some(child) = self.children.first_mut() else {
+            return;
+        };
+        let child = child.as_base_mut();
+        if !in_view {
+            match kind {
+                MouseEventKind::Moved => child.handle_mouse_leave(),
+                MouseEventKind::Down(_) => child.handle_mouse(0, 0, None),
+                _ => {}
+            }
+            return;
+        }
+        if let MouseEventKind::Down(_) = kind {
+            self.capture = true;
+        }
+        child.handle_mouse(x + self.offset_x, y + self.offset_y, Some(kind));
+    }
+
+    fn handle_mouse_leave(&mut self) {
+        if let Some(child) = self.children.first_mut() {
+            child.as_base_mut().handle_mouse_leave();
+        }
+    }
+
+    fn handle_mouse_captured(&mut self, x: i32, y: i32, kind: MouseEventKind) {
+        if let Some(direction) = self.scrollbar_drag.clone() {
+            self.handle_scrollbar(direction, x, y);
+        } else if let (true, Some(child)) = (self.capture, self.children.first_mut()) {
+            child.as_base_mut().handle_mouse_captured(
+                x + self.offset_x as i32,
+                y + self.offset_y as i32,
+                kind,
+            );
+        }
+        if let MouseEventKind::Up(_) = kind {
+            self.capture = false;
+            self.scrollbar_drag = None;
+        }
+    }
+
+    fn handle_click(&mut self, x: u16, y: u16, button: MouseButton, count: u8) {
+        if !self.in_view(x, y) {
+            return;
+        }
+        let (offset_x, offset_y) = self.get_offset();
+        if let Some(child) = self.children.first_mut() {
+            child
+                .as_base_mut()
+                .handle_click(x + offset_x, y + offset_y, button, count);
+        }
+    }
+
+    /// The wheel scrolls the content while it can, with shift scrolling
+    /// sideways, and only then reaches the child under the mouse
+    fn handle_scroll(&mut self, x: u16, y: u16, kind: MouseEventKind, modifiers: KeyModifiers) {
+        let step = match kind {
+            MouseEventKind::ScrollDown => self.scroll_step as i32,
+            _ => -(self.scroll_step as i32),
+        };
+        let scrolled = match modifiers.contains(KeyModifiers::SHIFT) {
+            true => self.scroll_by(step, 0),
+            false => self.scroll_by(0, step),
+        };
+        if scrolled || !self.in_view(x, y) {
+            return;
+        }
+        let (offset_x, offset_y) = self.get_offset();
+        if let Some(child) = self.children.first_mut() {
+            child
+                .as_base_mut()
+                .handle_scroll(x + offset_x, y + offset_y, kind, modifiers);
+        }
+    }
+
+    /// Keys go to the child first, keeping the focused component visible, and
+    /// the page keys scroll the content if the child did not use them
+    fn handle_key(&mut self, event: KeyEvent) -> EventResult {
+        let Some(child) = self.children.first_mut() else {
+            return EventResult::Ignored;
+        };
+        let result = child.as_base_mut().handle_key(event);
+        match result {
+            EventResult::Ignored => {}
+            EventResult::Border(_) => return result,
+            _ => {
+                self.focused_name = self.as_container().search_focused_name();
+                return match self.scroll_to_focused() {
+                    true => EventResult::Redraw,
+                    false => result,
+                };
+            }
+        }
+        let page = std::cmp::max(self.viewport.view_height, 1) as i32;
+        let scrolled = match event.code {
+            KeyCode::PageDown => self.scroll_by(0, page),
+            KeyCode::PageUp => self.scroll_by(0, -page),
+            KeyCode::Home => self.scroll_to(self.offset_x, 0),
+            KeyCode::End => self.scroll_to(self.offset_x, u16::MAX),
+            _ => return EventResult::Ignored,
+        };
+        match scrolled {
+            true => EventResult::Redraw,
+            false => EventResult::Ignored,
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) -> EventResult {
+        paste_focused(&mut self.children, text)
+    }
+
+    fn handle_terminal_focus(&mut self, focused: bool) {
+        for_each_child(&mut self.children, |c| c.handle_terminal_focus(focused));
+    }
+
+    fn handle_update(&mut self) {
+        self.follow_focus();
+        for_each_child(&mut self.children, |c| c.handle_update());
+    }
+
+    fn invalidate(&mut self) {
+        for_each_child(&mut self.children, |c| c.invalidate());
+    }
+
+    fn resize(&mut self, width: u16, height: u16) -> Result<(), ResizeError> {
+        if self.width == width && self.height == height {
+            return Ok(());
+        }
+        self.resize_children(width, height)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        assert_eq!(area.width, self.width);
+        assert_eq!(area.height, self.height);
+        let content_area = self.content.area;
+        if let Some(child) = self.children.first_mut() {
+            child.as_base_mut().render(content_area, &mut self.content);
+        }
+        // Copy the part of the content in the window, clearing the rest
+        for y in 0..self.viewport.view_height {
+            for x in 0..self.viewport.view_width {
+                let cell = buf.get_mut(area.x + x, area.y + y);
+                match self.in_view(x, y) {
+                    true => {
+                        *cell = self
+                            .content
+                            .get(x + self.offset_x, y + self.offset_y)
+                            .clone()
+                    }
+                    false => {
+                        cell.reset();
+                    }
+                }
+            }
+        }
+        if self.scrollbars {
+            self.render_scrollbars(area, buf);
+        }
+    }
+
+    fn get_width(&self) -> u16 {
+        self.width
+    }
+
+    fn get_height(&self) -> u16 {
+        self.height
+    }
+
+    fn is_fixed_width(&self) -> bool {
+        false
+    }
+
+    fn is_fixed_height(&self) -> bool {
+        false
+    }
+
+    /// The content scrolls instead of shrinking, so the container only needs
+    /// room for a single cell and a scrollbar
+    fn get_size_range(&self, _direction: Direction) -> (u16, u16) {
+        (1 + self.scrollbars as u16, u16::MAX)
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.children.iter().any(|c| c.as_base().is_focusable())
+    }
+
+    fn get_focus(&self) -> Focus {
+        get_children_focus(&self.children)
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_border(&self, x: u16, y: u16) -> Option<Border> {
+        if !self.in_view(x, y) {
+            return None;
+        }
+        let border = self
+            .children
+            .first()?
+            .as_base()
+            .get_border(x + self.offset_x, y + self.offset_y)?;
+        self.get_outer_border(x, y, border)
+    }
+
+    fn get_border_along(&self, x: u16, y: u16, direction: Direction) -> Option<Border> {
+        if !self.in_view(x, y) {
+            return None;
+        }
+        let border = self.children.first()?.as_base().get_border_along(
+            x + self.offset_x,
+            y + self.offset_y,
+            direction,
+        )?;
+        self.get_outer_border(x, y, border)
+    }
+}
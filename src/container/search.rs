@@ -3,20 +3,37 @@ use tui::layout::Rect;
 use crate::{
     component::{Component, ComponentBase, ComponentWidget},
     container::{Container, ContainerChild},
-    pos::ComponentPos,
+    pos::{clip_rect, ComponentPos},
     Focus, FocusResult,
 };
 
 pub trait ContainerSearch {
+    /// Returns the (partially) focused component and where it is shown, which
+    /// is clamped to the part in view for components scrolled out of view
     fn search_focused(&self) -> FocusResult<(&Component, ComponentPos)>;
     fn search_focused_mut(&mut self) -> FocusResult<(&mut Component, ComponentPos)>;
 
+    /// Returns the path of the (partially) focused component
+    fn search_focused_name(&self) -> Option<String>;
+
     fn search_position(&self, pos: ComponentPos) -> Option<(&Component, ComponentPos)>;
     fn search_position_mut(&mut self, pos: ComponentPos) -> Option<(&mut Component, ComponentPos)>;
 
+    /// Returns the first component that can take focus, skipping children
+    /// without any room in their container, such as collapsed sections
+    fn search_focusable(&self) -> Option<(&Component, ComponentPos)>;
+
+    /// Returns the paths and positions of all the components
+    fn search_components(&self) -> Vec<(String, &Component, ComponentPos)>;
+
+    /// Returns the paths of the components in view, where the part of each in
+    /// view is shown and that part in the coordinates of the component
+    fn search_component_views(&self) -> Vec<(String, &Component, ComponentPos, Rect)>;
+
     fn search_name(&self, path: &str) -> Option<(&ContainerChild, ComponentPos)>;
     fn search_name_mut(&mut self, path: &str) -> Option<(&mut ContainerChild, ComponentPos)>;
 
+    #[allow(clippy::question_mark)]
     fn search_name_widget<T>(&self, path: &str) -> Option<&T>
     where
         T: ComponentWidget + 'static,
@@ -33,6 +50,7 @@ pub trait ContainerSearch {
         Some(widget)
     }
 
+    #[allow(clippy::question_mark)]
     fn search_name_widget_mut<T>(&mut self, path: &str) -> Option<&mut T>
     where
         T: ComponentWidget + 'static,
@@ -50,30 +68,125 @@ pub trait ContainerSearch {
     }
 }
 
-fn get_positions(container: &dyn Container) -> Vec<ComponentPos> {
+/// Where a child is shown in a container and the part of the child shown there,
+/// in the coordinates of the child
+struct ChildView {
+    rect: Rect,
+    view: Rect,
+}
+
+impl ChildView {
+    /// Returns the position in the container of a position in the child,
+    /// clamped to the part of the child in view
+    fn to_container(&self, pos: ComponentPos) -> ComponentPos {
+        let clamp = |p: u16, start: u16, size: u16| {
+            p.max(start).min(start + size.saturating_sub(1)) - start
+        };
+        ComponentPos {
+            x: self.rect.x + clamp(pos.x, self.view.x, self.view.width),
+            y: self.rect.y + clamp(pos.y, self.view.y, self.view.height),
+        }
+    }
+
+    /// Returns the position in the container of a position in the child,
+    /// which is before or past the edges of the container if out of view
+    fn to_container_unclamped(&self, x: i32, y: i32) -> (i32, i32) {
+        (
+            self.rect.x as i32 + x - self.view.x as i32,
+            self.rect.y as i32 + y - self.view.y as i32,
+        )
+    }
+
+    /// Returns the position in the container of the child
+    fn origin(&self) -> ComponentPos {
+        self.to_container(ComponentPos::default())
+    }
+
+    /// Returns the position in the child of a position in the container
+    fn to_child(&self, pos: ComponentPos) -> Option<ComponentPos> {
+        let x = (pos.x.checked_sub(self.rect.x)?).checked_add(self.view.x)?;
+        let y = (pos.y.checked_sub(self.rect.y)?).checked_add(self.view.y)?;
+        Some(ComponentPos { x, y })
+    }
+
+    /// Returns where the part of a component shown at the position in the
+    /// child is shown in the container, and what is left of the part in view,
+    /// or none if the part is out of view
+    fn part_to_container(&self, pos: ComponentPos, part: Rect) -> Option<(ComponentPos, Rect)> {
+        let shown = Rect {
+            x: pos.x,
+            y: pos.y,
+            ..part
+        };
+        let shown = clip_rect(shown, self.view)?;
+        let part = Rect {
+            x: part.x + shown.x - pos.x,
+            y: part.y + shown.y - pos.y,
+            ..shown
+        };
+        let pos = ComponentPos {
+            x: self.rect.x + shown.x - self.view.x,
+            y: self.rect.y + shown.y - self.view.y,
+        };
+        Some((pos, part))
+    }
+}
+
+/// Returns where each child is shown in the container and the part shown
+fn get_child_views(container: &dyn Container) -> Vec<ChildView> {
     container
         .get_children_rectangles()
-        .iter()
-        .map(|r| ComponentPos { x: r.x, y: r.y })
-        .collect::<Vec<ComponentPos>>()
+        .into_iter()
+        .enumerate()
+        .map(|(i, rect)| ChildView {
+            rect,
+            view: container
+                .get_child_view(i)
+                .unwrap_or(Rect { x: 0, y: 0, ..rect }),
+        })
+        .collect()
+}
+
+/// Returns the position and size of the (partially) focused component, with
+/// the position left unclamped for components scrolled partly or fully out
+/// of view, so moving focus starts from where the component really is
+pub(crate) fn search_focused_area(container: &dyn Container) -> Option<(i32, i32, u16, u16)> {
+    let views = get_child_views(container);
+    for (i, child) in container.get_children().iter().enumerate() {
+        let (x, y, width, height) = match child {
+            ContainerChild::Component(child) if child.get_focus() != Focus::None => {
+                (0, 0, child.get_width(), child.get_height())
+            }
+            ContainerChild::Component(_) => continue,
+            ContainerChild::Container(child) => {
+                let Some(area) = search_focused_area(child.as_container()) else {
+                    continue;
+                };
+                area
+            }
+        };
+        let (x, y) = views[i].to_container_unclamped(x, y);
+        return Some((x, y, width, height));
+    }
+    None
 }
 
 impl<'a> ContainerSearch for dyn Container + 'a {
     fn search_focused(&self) -> FocusResult<(&Component, ComponentPos)> {
-        let offsets = get_positions(self);
+        let views = get_child_views(self);
         for (i, child) in self.get_children().iter().enumerate() {
             return match child {
                 ContainerChild::Component(child) => match child.get_focus() {
-                    Focus::Focus => FocusResult::Focus((child, offsets[i].clone())),
-                    Focus::PartialFocus => FocusResult::PartialFocus((child, offsets[i].clone())),
+                    Focus::Focus => FocusResult::Focus((child, views[i].origin())),
+                    Focus::PartialFocus => FocusResult::PartialFocus((child, views[i].origin())),
                     Focus::None => continue,
                 },
                 ContainerChild::Container(child) => match child.search_focused() {
                     FocusResult::Focus((child, pos)) => {
-                        FocusResult::Focus((child, offsets[i].clone() + pos))
+                        FocusResult::Focus((child, views[i].to_container(pos)))
                     }
                     FocusResult::PartialFocus((child, pos)) => {
-                        FocusResult::PartialFocus((child, offsets[i].clone() + pos))
+                        FocusResult::PartialFocus((child, views[i].to_container(pos)))
                     }
                     FocusResult::None => continue,
                 },
@@ -83,20 +196,20 @@ impl<'a> ContainerSearch for dyn Container + 'a {
     }
 
     fn search_focused_mut(&mut self) -> FocusResult<(&mut Component, ComponentPos)> {
-        let offsets = get_positions(self);
+        let views = get_child_views(self);
         for (i, child) in self.get_children_mut().iter_mut().enumerate() {
             return match child {
                 ContainerChild::Component(child) => match child.get_focus() {
-                    Focus::Focus => FocusResult::Focus((child, offsets[i].clone())),
-                    Focus::PartialFocus => FocusResult::PartialFocus((child, offsets[i].clone())),
+                    Focus::Focus => FocusResult::Focus((child, views[i].origin())),
+                    Focus::PartialFocus => FocusResult::PartialFocus((child, views[i].origin())),
                     Focus::None => continue,
                 },
                 ContainerChild::Container(child) => match child.search_focused_mut() {
                     FocusResult::Focus((child, pos)) => {
-                        FocusResult::Focus((child, offsets[i].clone() + pos))
+                        FocusResult::Focus((child, views[i].to_container(pos)))
                     }
                     FocusResult::PartialFocus((child, pos)) => {
-                        FocusResult::PartialFocus((child, offsets[i].clone() + pos))
+                        FocusResult::PartialFocus((child, views[i].to_container(pos)))
                     }
                     FocusResult::None => continue,
                 },
@@ -105,20 +218,36 @@ impl<'a> ContainerSearch for dyn Container + 'a {
         FocusResult::None
     }
 
+    fn search_focused_name(&self) -> Option<String> {
+        for child in self.get_children() {
+            match child {
+                ContainerChild::Component(child) if child.get_focus() != Focus::None => {
+                    return Some(child.get_name())
+                }
+                ContainerChild::Component(_) => continue,
+                ContainerChild::Container(child) => {
+                    if let Some(path) = child.search_focused_name() {
+                        return Some(format!("{}.{}", child.as_base().get_name(), path));
+                    }
+                }
+            }
+        }
+        None
+    }
+
     fn search_position(&self, pos: ComponentPos) -> Option<(&Component, ComponentPos)> {
-        let child_offsets = get_positions(self);
-        let child_rects = self.get_children_rectangles();
+        let views = get_child_views(self);
         let pos_rect = Rect::from(pos.clone());
         for (i, child) in self.get_children().iter().enumerate() {
-            if !child_rects[i].intersects(pos_rect) {
+            if !views[i].rect.intersects(pos_rect) {
                 continue;
             }
             return match child {
-                ContainerChild::Component(child) => Some((child, child_offsets[i].clone())),
+                ContainerChild::Component(child) => Some((child, views[i].origin())),
                 ContainerChild::Container(child) => {
-                    let new_pos = (pos - child_offsets[i].clone()).unwrap();
+                    let new_pos = views[i].to_child(pos)?;
                     if let Some((child, pos)) = child.search_position(new_pos) {
-                        Some((child, child_offsets[i].clone() + pos))
+                        Some((child, views[i].to_container(pos)))
                     } else {
                         None
                     }
@@ -129,19 +258,18 @@ impl<'a> ContainerSearch for dyn Container + 'a {
     }
 
     fn search_position_mut(&mut self, pos: ComponentPos) -> Option<(&mut Component, ComponentPos)> {
-        let child_offsets = get_positions(self);
-        let child_rects = self.get_children_rectangles();
+        let views = get_child_views(self);
         let pos_rect = Rect::from(pos.clone());
         for (i, child) in self.get_children_mut().iter_mut().enumerate() {
-            if !child_rects[i].intersects(pos_rect) {
+            if !views[i].rect.intersects(pos_rect) {
                 continue;
             }
             return match child {
-                ContainerChild::Component(child) => Some((child, child_offsets[i].clone())),
+                ContainerChild::Component(child) => Some((child, views[i].origin())),
                 ContainerChild::Container(child) => {
-                    let new_pos = (pos - child_offsets[i].clone()).unwrap();
+                    let new_pos = views[i].to_child(pos)?;
                     if let Some((child, pos)) = child.search_position_mut(new_pos) {
-                        Some((child, child_offsets[i].clone() + pos))
+                        Some((child, views[i].to_container(pos)))
                     } else {
                         None
                     }
@@ -151,27 +279,102 @@ impl<'a> ContainerSearch for dyn Container + 'a {
         None
     }
 
+    fn search_focusable(&self) -> Option<(&Component, ComponentPos)> {
+        let views = get_child_views(self);
+        for (i, child) in self.get_children().iter().enumerate() {
+            if views[i].rect.area() == 0 {
+                continue;
+            }
+            match child {
+                ContainerChild::Component(child) if child.is_focusable() => {
+                    return Some((child, views[i].origin()))
+                }
+                ContainerChild::Component(_) => continue,
+                ContainerChild::Container(child) => {
+                    if let Some((child, pos)) = child.search_focusable() {
+                        return Some((child, views[i].to_container(pos)));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn search_components(&self) -> Vec<(String, &Component, ComponentPos)> {
+        let views = get_child_views(self);
+        let mut components = Vec::new();
+        for (i, child) in self.get_children().iter().enumerate() {
+            match child {
+                ContainerChild::Component(child) => {
+                    components.push((child.get_name(), child, views[i].origin()))
+                }
+                ContainerChild::Container(container) => {
+                    let name = container.as_base().get_name();
+                    for (path, child, pos) in container.search_components() {
+                        components.push((
+                            format!("{}.{}", name, path),
+                            child,
+                            views[i].to_container(pos),
+                        ));
+                    }
+                }
+            }
+        }
+        components
+    }
+
+    fn search_component_views(&self) -> Vec<(String, &Component, ComponentPos, Rect)> {
+        let views = get_child_views(self);
+        let mut components = Vec::new();
+        for (i, child) in self.get_children().iter().enumerate() {
+            match child {
+                ContainerChild::Component(child) => {
+                    let part = Rect {
+                        x: 0,
+                        y: 0,
+                        width: child.get_width(),
+                        height: child.get_height(),
+                    };
+                    let pos = ComponentPos::default();
+                    if let Some((pos, part)) = views[i].part_to_container(pos, part) {
+                        components.push((child.get_name(), child, pos, part));
+                    }
+                }
+                ContainerChild::Container(container) => {
+                    let name = container.as_base().get_name();
+                    for (path, child, pos, part) in container.search_component_views() {
+                        if let Some((pos, part)) = views[i].part_to_container(pos, part) {
+                            components.push((format!("{}.{}", name, path), child, pos, part));
+                        }
+                    }
+                }
+            }
+        }
+        components
+    }
+
     fn search_name(&self, path: &str) -> Option<(&ContainerChild, ComponentPos)> {
         let (before, after) = if let Some((before, after)) = path.split_once('.') {
             (before, Some(after))
         } else {
             (path, None)
         };
-        let child_offsets = get_positions(self);
+        let views = get_child_views(self);
         for (i, child) in self.get_children().iter().enumerate() {
             if before != child.as_base().get_name() {
                 continue;
             }
+            // Path ends at this child, which may be a component or container
+            let Some(after) = after else {
+                return Some((child, views[i].origin()));
+            };
             return match child {
-                child @ ContainerChild::Component(_) => Some((child, child_offsets[i].clone())),
+                child @ ContainerChild::Component(_) => Some((child, views[i].origin())),
                 ContainerChild::Container(child) => {
-                    let Some(after) = after else {
-                        continue;
-                    };
                     let Some((child, pos)) = child.search_name(after) else {
-                        continue ;
+                        continue;
                     };
-                    Some((child, child_offsets[i].clone() + pos))
+                    Some((child, views[i].to_container(pos)))
                 }
             };
         }
@@ -184,21 +387,22 @@ impl<'a> ContainerSearch for dyn Container + 'a {
         } else {
             (path, None)
         };
-        let child_offsets = get_positions(self);
+        let views = get_child_views(self);
         for (i, child) in self.get_children_mut().iter_mut().enumerate() {
             if before != child.as_base().get_name() {
                 continue;
             }
+            // Path ends at this child, which may be a component or container
+            let Some(after) = after else {
+                return Some((child, views[i].origin()));
+            };
             return match child {
-                child @ ContainerChild::Component(_) => Some((child, child_offsets[i].clone())),
+                child @ ContainerChild::Component(_) => Some((child, views[i].origin())),
                 ContainerChild::Container(child) => {
-                    let Some(after) = after else {
-                        continue;
-                    };
                     let Some((child, pos)) = child.search_name_mut(after) else {
-                        continue ;
+                        continue;
                     };
-                    Some((child, child_offsets[i].clone() + pos))
+                    Some((child, views[i].to_container(pos)))
                 }
             };
         }
@@ -0,0 +1,633 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+use tui::{
+    buffer::Buffer,
+    layout::{Direction, Rect},
+    style::{Color, Style},
+};
+
+use crate::{container::list::*, container::*, Border, EventResult, Focus, MenuItem, ResizeError};
+
+/// Provides the children of a virtual container by index, creating them only
+/// when they are scrolled into view
+pub trait ChildSource {
+    /// Returns the number of children the container can scroll through
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Creates the child at the index, which is resized by the container
+    /// before it is shown
+    fn get_child(&mut self, index: usize) -> ContainerChild;
+
+    /// Takes back the child at the index once it is scrolled out of view, so
+    /// that any state in it can be kept for when it is created again
+    fn release_child(&mut self, _index: usize, _child: ContainerChild) {}
+}
+
+/// Container listing children of the same size from a source, only keeping
+/// the children in view and getting the rest from the source when scrolled
+pub struct ContainerVirtual {
+    name: String,
+    orientation: Direction,
+    item_size: u16,
+    source: Box<dyn ChildSource>,
+    first: usize,
+    scroll_step: u16,
+    capture: Option<usize>,
+    scrollbar_drag: bool,
+    width: u16,
+    height: u16,
+    children: Vec<ContainerChild>,
+    menu_items: Vec<MenuItem>,
+}
+
+impl ContainerVirtual {
+    /// Creates a container showing the children of the source in order, each
+    /// one item size long along the orientation
+    pub fn new(
+        name: String,
+        orientation: Direction,
+        item_size: u16,
+        source: Box<dyn ChildSource>,
+        width: u16,
+        height: u16,
+    ) -> Self {
+        Self {
+            name,
+            orientation,
+            item_size: std::cmp::max(item_size, 1),
+            source,
+            first: 0,
+            scroll_step: 1,
+            capture: None,
+            scrollbar_drag: false,
+            width,
+            height,
+            children: Vec::new(),
+            menu_items: Vec::new(),
+        }
+    }
+
+    pub fn get_orientation(&self) -> Direction {
+        self.orientation.clone()
+    }
+
+    pub fn get_item_size(&self) -> u16 {
+        self.item_size
+    }
+
+    pub fn get_source(&self) -> &dyn ChildSource {
+        self.source.as_ref()
+    }
+
+    pub fn get_source_mut(&mut self) -> &mut dyn ChildSource {
+        self.source.as_mut()
+    }
+
+    pub fn get_scroll_step(&self) -> u16 {
+        self.scroll_step
+    }
+
+    /// Sets how many children the container moves for each step of the mouse
+    /// wheel
+    pub fn set_scroll_step(&mut self, scroll_step: u16) {
+        self.scroll_step = scroll_step;
+    }
+
+    pub fn scroll_step(mut self, scroll_step: u16) -> Self {
+        self.set_scroll_step(scroll_step);
+        self
+    }
+
+    /// Sets the context menu items shown for right clicks on any child
+    pub fn set_menu_items(&mut self, menu_items: Vec<MenuItem>) {
+        self.menu_items = menu_items;
+    }
+
+    pub fn menu_items(mut self, menu_items: Vec<MenuItem>) -> Self {
+        self.set_menu_items(menu_items);
+        self
+    }
+
+    /// Returns the index in the source of the first child in view
+    pub fn get_first(&self) -> usize {
+        self.first
+    }
+
+    /// Scrolls so the child at the index is the first in view, stopping at
+    /// the end, and returns false if the children in view did not change
+    pub fn scroll_to(&mut self, index: usize) -> Result<bool, ResizeError> {
+        let first = index.min(self.get_max_first(self.width, self.height));
+        if first == self.first {
+            return Ok(false);
+        }
+        self.load(first, self.width, self.height)?;
+        Ok(true)
+    }
+
+    /// Scrolls by the number of children, stopping at either end, and returns
+    /// false if the children in view did not change
+    pub fn scroll_by(&mut self, delta: isize) -> Result<bool, ResizeError> {
+        self.scroll_to(self.first.saturating_add_signed(delta))
+    }
+
+    /// Gives all the children in view back to the source and gets them again,
+    /// for when the source has changed
+    pub fn reload(&mut self) -> Result<(), ResizeError> {
+        for (i, child) in std::mem::take(&mut self.children).into_iter().enumerate() {
+            self.release_child(self.first + i, child);
+        }
+        let first = self.first.min(self.get_max_first(self.width, self.height));
+        self.load(first, self.width, self.height)
+    }
+
+    /// Returns the length of the container along the orientation
+    fn get_length(&self, width: u16, height: u16) -> u16 {
+        match self.orientation {
+            Direction::Horizontal => width,
+            Direction::Vertical => height,
+        }
+    }
+
+    /// Returns the number of children that fit in the container
+    fn get_page(&self, width: u16, height: u16) -> usize {
+        (self.get_length(width, height) / self.item_size) as usize
+    }
+
+    /// Returns the index of the first child in view when scrolled to the end
+    fn get_max_first(&self, width: u16, height: u16) -> usize {
+        self.source
+            .len()
+            .saturating_sub(self.get_page(width, height))
+    }
+
+    /// Returns the size of each child, leaving room across the container for
+    /// the scrollbar
+    fn get_child_size(&self, width: u16, height: u16) -> (u16, u16) {
+        match self.orientation {
+            Direction::Horizontal => (self.item_size, height.saturating_sub(1)),
+            Direction::Vertical => (width.saturating_sub(1), self.item_size),
+        }
+    }
+
+    /// Clears the focus and hover of the child before giving it back to the
+    /// source
+    fn release_child(&mut self, index: usize, mut child: ContainerChild) {
+        child.as_base_mut().handle_mouse(0, 0, None);
+        child.as_base_mut().handle_mouse_leave();
+        self.source.release_child(index, child);
+    }
+
+    /// Makes the children from the index onwards the ones in view for the
+    /// size, keeping the children that stay in view and getting the new ones
+    /// from the source, or leaves the children in view as they were if any of
+    /// them could not be resized
+    fn load(&mut self, first: usize, width: u16, height: u16) -> Result<(), ResizeError> {
+        let count = std::cmp::min(
+            self.get_page(width, height),
+            self.source.len().saturating_sub(first),
+        );
+        let (child_width, child_height) = self.get_child_size(width, height);
+        let old_first = self.first;
+        let mut old = std::mem::take(&mut self.children)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<Option<ContainerChild>>>();
+        let old_dimensions = old
+            .iter()
+            .flatten()
+            .map(|c| (c.as_base().get_width(), c.as_base().get_height()))
+            .collect::<Vec<(u16, u16)>>();
+        let mut children = Vec::new();
+        for index in first..first + count {
+            let kept = index
+                .checked_sub(old_first)
+                .and_then(|i| old.get_mut(i))
+                .and_then(Option::take);
+            let mut child = match kept {
+                Some(child) => child,
+                None => self.source.get_child(index),
+            };
+            let result = child.as_base_mut().resize(child_width, child_height);
+            children.push(child);
+            let Err(err) = result else {
+                continue;
+            };
+            // Put back the children that were in view and return the rest
+            for (index, child) in (first..).zip(children) {
+                match index.checked_sub(old_first).and_then(|i| old.get_mut(i)) {
+                    Some(slot) => *slot = Some(child),
+                    None => self.release_child(index, child),
+                }
+            }
+            self.children = old.into_iter().flatten().collect();
+            for (child, dim) in self.children.iter_mut().zip(old_dimensions) {
+                let _ = child.as_base_mut().resize(dim.0, dim.1);
+            }
+            return Err(err);
+        }
+        for (i, child) in old.into_iter().enumerate() {
+            if let Some(child) = child {
+                self.release_child(old_first + i, child);
+            }
+        }
+        self.first = first;
+        self.children = children;
+        self.width = width;
+        self.height = height;
+        self.capture = None;
+        self.scrollbar_drag = false;
+        self.invalidate();
+        Ok(())
+    }
+
+    /// Moves focus from the focused component to the next component across
+    /// the border, scrolling one child at a time to reach the children out
+    /// of view, and returns the border if there is no such component
+    fn handle_focus_move(&mut self, border: Border) -> EventResult {
+        let border = match move_focus(self, border) {
+            EventResult::Border(border) => border,
+            result => return result,
+        };
+        let Some(((x, y), width, height)) = get_focused_position(self) else {
+            return EventResult::Border(border);
+        };
+        let delta = match (&self.orientation, &border) {
+            (Direction::Vertical, Border::Bottom) | (Direction::Horizontal, Border::Right) => 1,
+            (Direction::Vertical, Border::Top) | (Direction::Horizontal, Border::Left) => -1,
+            _ => return EventResult::Border(border),
+        };
+        // Keep focus where it is at either end of the list
+        if !matches!(self.scroll_by(delta), Ok(true)) {
+            return EventResult::Border(border);
+        }
+        clear_focus(self);
+        // The focused component moved by a child in the other direction, out
+        // of view if it was at the edge
+        let shift = self.item_size as i32 * -delta as i32;
+        let moved = match self.orientation {
+            Direction::Horizontal => (x + shift, y),
+            Direction::Vertical => (x, y + shift),
+        };
+        if let Some(next_pos) = find_next_pos(self, moved, border, width, height) {
+            focus_position(self, next_pos);
+        }
+        EventResult::Redraw
+    }
+
+    /// Returns true if the position is on the scrollbar
+    fn is_scrollbar(&self, x: u16, y: u16) -> bool {
+        match self.orientation {
+            Direction::Horizontal => y + 1 == self.height,
+            Direction::Vertical => x + 1 == self.width,
+        }
+    }
+
+    /// Scrolls so the thumb of the scrollbar is at the position
+    fn handle_scrollbar(&mut self, x: i32, y: i32) {
+        let pos = match self.orientation {
+            Direction::Horizontal => x,
+            Direction::Vertical => y,
+        };
+        let length = self.get_length(self.width, self.height) as i64;
+        let max_first = self.get_max_first(self.width, self.height) as i64;
+        let first = match length {
+            0 | 1 => 0,
+            _ => pos.clamp(0, length as i32 - 1) as i64 * max_first / (length - 1),
+        };
+        let _ = self.scroll_to(first as usize);
+    }
+
+    /// Returns the border of the child if it is also on the outside of this
+    /// container
+    fn get_outer_border(&self, rect: Rect, border: Border) -> Option<Border> {
+        // The scrollbar is between the children and the outside
+        let (bar_width, bar_height) = match self.orientation {
+            Direction::Horizontal => (0, 1),
+            Direction::Vertical => (1, 0),
+        };
+        let outer = match border {
+            Border::Left => rect.x == 0,
+            Border::Top => rect.y == 0,
+            Border::Right => rect.x + rect.width + bar_width >= self.width,
+            Border::Bottom => rect.y + rect.height + bar_height >= self.height,
+        };
+        outer.then_some(border)
+    }
+
+    /// Draws the scrollbar with the thumb showing which children are in view
+    fn render_scrollbar(&self, area: Rect, buf: &mut Buffer) {
+        let length = self.get_length(area.width, area.height) as u64;
+        let page = self.get_page(area.width, area.height) as u64;
+        let total = std::cmp::max(self.source.len() as u64, 1);
+        let max_first = self.get_max_first(area.width, area.height) as u64;
+        let thumb = std::cmp::max(length * page.min(total) / total, 1).min(length);
+        let start = match max_first {
+            0 => 0,
+            _ => self.first as u64 * (length - thumb) / max_first,
+        };
+        for i in 0..length as u16 {
+            let (x, y) = match self.orientation {
+                Direction::Horizontal => (area.x + i, area.y + area.height - 1),
+                Direction::Vertical => (area.x + area.width - 1, area.y + i),
+            };
+            let (symbol, style) = match (i as u64) >= start && (i as u64) < start + thumb {
+                true => ("█", Style::default().fg(Color::White)),
+                false => match self.orientation {
+                    Direction::Horizontal => ("─", Style::default().fg(Color::DarkGray)),
+                    Direction::Vertical => ("│", Style::default().fg(Color::DarkGray)),
+                },
+            };
+            buf.get_mut(x, y).set_symbol(symbol).set_style(style);
+        }
+    }
+}
+
+impl Container for ContainerVirtual {
+    fn get_children(&self) -> &Vec<ContainerChild> {
+        &self.children
+    }
+
+    fn get_children_mut(&mut self) -> &mut Vec<ContainerChild> {
+        &mut self.children
+    }
+
+    /// Only the children in view have rectangles, one item size apart
+    fn get_children_rectangles(&self) -> Vec<Rect> {
+        let (width, height) = self.get_child_size(self.width, self.height);
+        (0..self.children.len() as u16)
+            .map(|i| match self.orientation {
+                Direction::Horizontal => Rect {
+                    x: i * self.item_size,
+                    y: 0,
+                    width,
+                    height,
+                },
+                Direction::Vertical => Rect {
+                    x: 0,
+                    y: i * self.item_size,
+                    width,
+                    height,
+                },
+            })
+            .collect()
+    }
+
+    fn as_base(&self) -> &dyn ComponentBase {
+        self
+    }
+
+    fn as_base_mut(&mut self) -> &mut dyn ComponentBase {
+        self
+    }
+
+    fn is_resizable(&self) -> bool {
+        false
+    }
+
+    fn refresh(&mut self) -> Result<(), ResizeError> {
+        self.load(self.first, self.width, self.height)
+    }
+
+    fn get_menu_items(&self) -> Vec<MenuItem> {
+        self.menu_items.clone()
+    }
+
+    fn equalize(&mut self, recursive: bool) -> Result<(), ResizeError> {
+        if !recursive {
+            return Ok(());
+        }
+        for child in &mut self.children {
+            if let ContainerChild::Container(container) = child {
+                container.equalize(true)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The children belong to the source, so they are removed there instead
+    fn remove_child(&mut self, _name: &str) -> Option<ContainerChild> {
+        None
+    }
+
+    fn as_container(&self) -> &dyn Container {
+        self
+    }
+
+    fn as_container_mut(&mut self) -> &mut dyn Container {
+        self
+    }
+}
+
+impl ComponentBase for ContainerVirtual {
+    fn handle_mouse(&mut self, x: u16, y: u16, kind: Option<MouseEventKind>) {
+        // Check if the mouse event is none
+        let Some(kind) = kind else {
+            // Issue none to all children
+            for_each_child(&mut self.children, |c| c.handle_mouse(0, 0, None));
+            self.capture = None;
+            self.scrollbar_drag = false;
+            return;
+        };
+        // Send mouse move events to the child under the mouse, letting the rest
+        // know the mouse left them without changing focus
+        if let MouseEventKind::Moved = kind {
+            hover_child(self, x, y);
+            return;
+        }
+        // Scroll events go to handle_scroll instead, which has the modifiers
+        if let MouseEventKind::ScrollDown | MouseEventKind::ScrollUp = kind {
+            return;
+        }
+        // Drags and the release go to whatever the drag started on
+        if let (true, MouseEventKind::Drag(_) | MouseEventKind::Up(_)) = (self.scrollbar_drag, kind)
+        {
+            self.handle_mouse_captured(x as i32, y as i32, kind);
+            return;
+        }
+        if let (Some(index), MouseEventKind::Drag(_) | MouseEventKind::Up(_)) = (self.capture, kind)
+        {
+            if let MouseEventKind::Up(_) = kind {
+                self.capture = None;
+            }
+            handle_captured_child(self, index, x as i32, y as i32, kind);
+            return;
+        }
+        if let (true, MouseEventKind::Down(MouseButton::Left)) = (self.is_scrollbar(x, y), kind) {
+            self.handle_scrollbar(x as i32, y as i32);
+            self.scrollbar_drag = true;
+            return;
+        }
+        let index = get_child_index(self, x, y);
+        if let MouseEventKind::Down(_) = kind {
+            self.capture = index;
+        }
+        // Dispatch the mouse event to the child under the mouse, issuing none
+        // to the rest
+        dispatch_child(self, index, x, y, kind);
+    }
+
+    fn handle_mouse_leave(&mut self) {
+        for_each_child(&mut self.children, |c| c.handle_mouse_leave());
+    }
+
+    fn handle_mouse_captured(&mut self, x: i32, y: i32, kind: MouseEventKind) {
+        if self.scrollbar_drag {
+            self.handle_scrollbar(x, y);
+            if let MouseEventKind::Up(_) = kind {
+                self.scrollbar_drag = false;
+            }
+        } else if let Some(index) = self.capture {
+            if let MouseEventKind::Up(_) = kind {
+                self.capture = None;
+            }
+            handle_captured_child(self, index, x, y, kind);
+        }
+    }
+
+    fn handle_click(&mut self, x: u16, y: u16, button: MouseButton, count: u8) {
+        click_child(self, x, y, button, count);
+    }
+
+    /// The wheel scrolls through the children while it can, and only then
+    /// reaches the child under the mouse
+    fn handle_scroll(&mut self, x: u16, y: u16, kind: MouseEventKind, modifiers: KeyModifiers) {
+        let delta = match kind {
+            MouseEventKind::ScrollDown => self.scroll_step as isize,
+            _ => -(self.scroll_step as isize),
+        };
+        if let Ok(true) = self.scroll_by(delta) {
+            return;
+        }
+        scroll_child(self, x, y, kind, modifiers);
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> EventResult {
+        // Send key event to the child with (partial) focus
+        let focused = get_focused_index(&self.children);
+        if let Some(index) = focused {
+            match self.children[index].as_base_mut().handle_key(event) {
+                EventResult::Border(border) => return self.handle_focus_move(border),
+                EventResult::Ignored => {}
+                result => return result,
+            }
+        }
+        // The page keys scroll through the children if no child used them
+        let page = std::cmp::max(self.get_page(self.width, self.height), 1) as isize;
+        let scrolled = match event.code {
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::Home => self.scroll_to(0),
+            KeyCode::End => self.scroll_to(usize::MAX),
+            _ if focused.is_none() => return focus_focusable(self, event),
+            _ => return EventResult::Ignored,
+        };
+        match scrolled {
+            Ok(true) => EventResult::Redraw,
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) -> EventResult {
+        paste_focused(&mut self.children, text)
+    }
+
+    fn handle_terminal_focus(&mut self, focused: bool) {
+        for_each_child(&mut self.children, |c| c.handle_terminal_focus(focused));
+    }
+
+    fn handle_update(&mut self) {
+        for_each_child(&mut self.children, |c| c.handle_update());
+    }
+
+    fn invalidate(&mut self) {
+        for_each_child(&mut self.children, |c| c.invalidate());
+    }
+
+    fn resize(&mut self, width: u16, height: u16) -> Result<(), ResizeError> {
+        if self.width == width && self.height == height {
+            return Ok(());
+        }
+        // Keep the same first child in view unless the end would show empty
+        let max_first = self.get_max_first(width, height);
+        self.load(self.first.min(max_first), width, height)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        assert_eq!(area.width, self.width);
+        assert_eq!(area.height, self.height);
+        // Clear the space after the last child, which no child draws over
+        let used = self.children.len() as u16 * self.item_size;
+        for x in area.left()..area.right() {
+            for y in area.top()..area.bottom() {
+                let along = match self.orientation {
+                    Direction::Horizontal => x - area.x,
+                    Direction::Vertical => y - area.y,
+                };
+                if along >= used {
+                    buf.get_mut(x, y).reset();
+                }
+            }
+        }
+        render_children(self, area, buf);
+        self.render_scrollbar(area, buf);
+    }
+
+    fn get_width(&self) -> u16 {
+        self.width
+    }
+
+    fn get_height(&self) -> u16 {
+        self.height
+    }
+
+    fn is_fixed_width(&self) -> bool {
+        false
+    }
+
+    fn is_fixed_height(&self) -> bool {
+        false
+    }
+
+    /// The container needs room for one child along the orientation and for
+    /// the scrollbar across it
+    fn get_size_range(&self, direction: Direction) -> (u16, u16) {
+        match direction == self.orientation {
+            true => (self.item_size, u16::MAX),
+            false => (2, u16::MAX),
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.children.iter().any(|c| c.as_base().is_focusable())
+    }
+
+    fn get_focus(&self) -> Focus {
+        get_children_focus(&self.children)
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_border(&self, x: u16, y: u16) -> Option<Border> {
+        let index = get_child_index(self, x, y)?;
+        let rect = self.as_container().get_children_rectangles()[index];
+        let border = self.children[index]
+            .as_base()
+            .get_border(x - rect.x, y - rect.y)?;
+        self.get_outer_border(rect, border)
+    }
+
+    fn get_border_along(&self, x: u16, y: u16, direction: Direction) -> Option<Border> {
+        let index = get_child_index(self, x, y)?;
+        let rect = self.as_container().get_children_rectangles()[index];
+        let border =
+            self.children[index]
+                .as_base()
+                .get_border_along(x - rect.x, y - rect.y, direction)?;
+        self.get_outer_border(rect, border)
+    }
+}
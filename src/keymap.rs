@@ -0,0 +1,170 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::KeyEvent;
+
+use crate::KeyBinding;
+
+/// Result of feeding a key into a keymap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyMatch<T> {
+    /// A sequence of keys completed a binding
+    Binding(T),
+    /// A key that is not part of any binding and should be handled normally
+    Key(KeyEvent),
+}
+
+/// Matches sequences of keys (chords such as `g g` or `Ctrl-w h`) to values
+///
+/// Keys are held while they are the start of a longer binding. If a binding
+/// is also the start of a longer binding, the longer binding is preferred
+/// until a key breaks the sequence or the timeout passes, at which point the
+/// shorter binding is used. Held keys that end up not matching any binding are
+/// given back to be handled normally.
+pub struct Keymap<T> {
+    bindings: Vec<(Vec<KeyBinding>, T)>,
+    pending: Vec<KeyEvent>,
+    pending_last: Option<Instant>,
+    timeout: Duration,
+}
+
+impl<T: Clone> Keymap<T> {
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+            pending: Vec::new(),
+            pending_last: None,
+            timeout: Duration::from_secs(1),
+        }
+    }
+
+    pub fn get_bindings(&self) -> &Vec<(Vec<KeyBinding>, T)> {
+        &self.bindings
+    }
+
+    /// Binds a sequence of keys to a value, replacing any existing binding
+    /// for the same sequence
+    pub fn bind(&mut self, keys: Vec<KeyBinding>, value: T) {
+        if keys.is_empty() {
+            return;
+        }
+        self.unbind(&keys);
+        self.bindings.push((keys, value));
+    }
+
+    pub fn unbind(&mut self, keys: &[KeyBinding]) {
+        self.bindings.retain(|(k, _)| k != keys);
+    }
+
+    pub fn with_binding(mut self, keys: Vec<KeyBinding>, value: T) -> Self {
+        self.bind(keys, value);
+        self
+    }
+
+    pub fn get_timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Sets how long to wait for the next key of a sequence
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.set_timeout(timeout);
+        self
+    }
+
+    /// Returns the keys being held while waiting for a sequence to complete
+    pub fn get_pending(&self) -> &Vec<KeyEvent> {
+        &self.pending
+    }
+
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Returns the remaining keys and values of all the bindings that could
+    /// still be completed from the pending keys
+    pub fn get_completions(&self) -> Vec<(Vec<KeyBinding>, T)> {
+        if self.pending.is_empty() {
+            return Vec::new();
+        }
+        self.bindings
+            .iter()
+            .filter(|(keys, _)| keys.len() > self.pending.len() && self.starts(keys, &self.pending))
+            .map(|(keys, value)| (keys[self.pending.len()..].to_vec(), value.clone()))
+            .collect()
+    }
+
+    /// Feeds a key into the keymap, returning the bindings completed and the
+    /// keys to handle normally, in the order they happened
+    pub fn handle_key(&mut self, e: KeyEvent) -> Vec<KeyMatch<T>> {
+        self.pending.push(e);
+        self.pending_last = Some(Instant::now());
+        self.resolve(false)
+    }
+
+    /// Resolves any pending keys if the timeout has passed since the last key
+    pub fn handle_timeout(&mut self) -> Vec<KeyMatch<T>> {
+        match self.pending_last {
+            Some(last) if last.elapsed() > self.timeout => self.resolve(true),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Drops any pending keys without matching them
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.pending_last = None;
+    }
+
+    fn starts(&self, keys: &[KeyBinding], events: &[KeyEvent]) -> bool {
+        keys.len() >= events.len() && keys.iter().zip(events).all(|(k, e)| k.matches(e))
+    }
+
+    fn find_exact(&self, events: &[KeyEvent]) -> Option<T> {
+        self.bindings
+            .iter()
+            .find(|(keys, _)| keys.len() == events.len() && self.starts(keys, events))
+            .map(|(_, value)| value.clone())
+    }
+
+    /// Matches as much of the pending keys as possible, waiting for more keys
+    /// if a longer binding could still match unless flushing
+    fn resolve(&mut self, flush: bool) -> Vec<KeyMatch<T>> {
+        let mut matches = Vec::new();
+        while !self.pending.is_empty() {
+            let longer = self.bindings.iter().any(|(keys, _)| {
+                keys.len() > self.pending.len() && self.starts(keys, &self.pending)
+            });
+            if longer && !flush {
+                return matches;
+            }
+            if let Some(value) = self.find_exact(&self.pending) {
+                matches.push(KeyMatch::Binding(value));
+                self.pending.clear();
+                break;
+            }
+            // Use the longest binding at the start of the pending keys, or give
+            // back the first key, and then try again with the rest
+            let found = (1..self.pending.len())
+                .rev()
+                .find_map(|n| self.find_exact(&self.pending[..n]).map(|v| (n, v)));
+            match found {
+                Some((n, value)) => {
+                    matches.push(KeyMatch::Binding(value));
+                    self.pending.drain(..n);
+                }
+                None => matches.push(KeyMatch::Key(self.pending.remove(0))),
+            }
+        }
+        self.pending_last = None;
+        matches
+    }
+}
+
+impl<T: Clone> Default for Keymap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,6 +1,10 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
 pub mod component;
 pub mod container;
+pub mod keymap;
 pub mod pos;
+pub mod root;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ResizeError {
@@ -30,3 +34,117 @@ pub enum Border {
     Left,
     Right,
 }
+
+impl Border {
+    /// Returns the border on the other side
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::Top => Self::Bottom,
+            Self::Bottom => Self::Top,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// Result of handling an input event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventResult {
+    /// The event was used and nothing needs to be redrawn
+    Consumed,
+    /// The event was not used and is passed up to the enclosing container
+    Ignored,
+    /// The event was used and the component needs to be redrawn
+    Redraw,
+    /// Focus should move to the next component across the border
+    Border(Border),
+    /// The event was used and produced an application defined action, which
+    /// is passed up to the application
+    Action(String),
+}
+
+/// Handles keys that were ignored by all the focused children of a container
+pub type KeyHandler = Box<dyn FnMut(KeyEvent) -> EventResult>;
+
+/// An entry in a context menu, sending the action back to whatever supplied
+/// the item when chosen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem {
+    pub label: String,
+    pub action: String,
+}
+
+impl MenuItem {
+    pub fn new(label: &str, action: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            action: action.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Returns if the key press or repeat matches this binding, ignoring the
+    /// state and treating shift on a character as part of the character
+    pub fn matches(&self, e: &KeyEvent) -> bool {
+        if !matches!(e.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+            return false;
+        }
+        normalize_key(self.code, self.modifiers) == normalize_key(e.code, e.modifiers)
+    }
+}
+
+/// Drops shift from characters, since terminals send shifted characters both
+/// with and without it, uppercasing the character if it was shifted
+fn normalize_key(code: KeyCode, modifiers: KeyModifiers) -> (KeyCode, KeyModifiers) {
+    match code {
+        KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => (
+            KeyCode::Char(c.to_ascii_uppercase()),
+            modifiers - KeyModifiers::SHIFT,
+        ),
+        code => (code, modifiers),
+    }
+}
+
+impl std::fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "C-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "M-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "S-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+impl From<KeyEvent> for KeyBinding {
+    fn from(e: KeyEvent) -> Self {
+        let (code, modifiers) = normalize_key(e.code, e.modifiers);
+        Self::new(code, modifiers)
+    }
+}
+
+impl From<KeyCode> for KeyBinding {
+    fn from(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::empty())
+    }
+}
@@ -65,3 +65,9 @@ impl ComponentPos {
         self.x >= r.x && self.x < (r.x + r.width) && self.y >= r.y && self.y < (r.y + r.height)
     }
 }
+
+/// Returns the part of the rectangle inside the area, or none if they do not
+/// overlap
+pub(crate) fn clip_rect(rect: Rect, area: Rect) -> Option<Rect> {
+    rect.intersects(area).then(|| rect.intersection(area))
+}
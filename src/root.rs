@@ -0,0 +1,2630 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{
+    Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton,
+    MouseEventKind,
+};
+use tui::{
+    buffer::Buffer,
+    layout::{Direction, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, BorderType, Borders, Clear, Widget},
+};
+
+use crate::{
+    component::{
+        handle_input_default, simple::ComponentWidgetSimple, Component, ComponentBase, FocusPolicy,
+    },
+    container::{
+        list::{find_next_pos, get_focused_position, ContainerList},
+        search::ContainerSearch,
+        Container, ContainerChild,
+    },
+    keymap::{KeyMatch, Keymap},
+    pos::{clip_rect, ComponentPos},
+    Border, EventResult, Focus, FocusResult, KeyBinding, KeyHandler, MenuItem, ResizeError,
+};
+
+/// Creates the new component when splitting the component at the given path
+pub type SplitFactory = Box<dyn FnMut(&str) -> Component>;
+
+/// Layout commands that can be bound to keys and run by the root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RootCommand {
+    /// Focuses the previously focused component
+    FocusPrevious,
+    /// Shows the quick jump labels
+    QuickJump,
+    /// Moves focus to the next component across the border
+    FocusMove(Border),
+    /// Splits the focused component in two along the direction
+    Split(Direction),
+    /// Removes the focused component
+    Close,
+    /// Toggles the focused component taking up the whole root
+    Zoom,
+    /// Grows the focused component by moving its border outwards by a number
+    /// of cells, or shrinks it if negative
+    Resize(Border, i16),
+    /// Starts moving the borders of the focused component with the arrow keys
+    ResizeMode,
+    /// Sets the children of the container holding the focused component to
+    /// the same size, including nested containers if true
+    Equalize(bool),
+    /// Moves the children of the container holding the focused component to
+    /// the next position, or the previous if true
+    Rotate(bool),
+    /// Swaps the focused component with the next component across the border
+    Swap(Border),
+    /// Switches the orientation of the container holding the focused component
+    Flip,
+    /// Switches the container holding the focused component to its next
+    /// layout
+    NextLayout,
+    /// Switches to the workspace at the index
+    Workspace(usize),
+    /// Switches to the next workspace, or the previous if true
+    NextWorkspace(bool),
+}
+
+/// When a hotkey is checked relative to sending the key to the focused
+/// component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HotkeyPriority {
+    /// Checked before the key is sent to the focused component
+    Before,
+    /// Checked only if the key is ignored by the focused component and all
+    /// of its containers
+    After,
+}
+
+/// What happens when a hotkey is pressed
+pub enum HotkeyAction {
+    /// Returns the action to the application
+    Action(String),
+    /// Calls the handler and returns its result to the application
+    Callback(KeyHandler),
+}
+
+struct Hotkey {
+    key: KeyBinding,
+    priority: HotkeyPriority,
+    action: HotkeyAction,
+}
+
+/// Default bindings for keys following the prefix key, modelled after tmux
+fn default_prefix_bindings() -> Vec<(KeyBinding, RootCommand)> {
+    vec![
+        (
+            KeyBinding::from(KeyCode::Char('%')),
+            RootCommand::Split(Direction::Horizontal),
+        ),
+        (
+            KeyBinding::from(KeyCode::Char('"')),
+            RootCommand::Split(Direction::Vertical),
+        ),
+        (KeyBinding::from(KeyCode::Char('x')), RootCommand::Close),
+        (KeyBinding::from(KeyCode::Char('z')), RootCommand::Zoom),
+        (
+            KeyBinding::from(KeyCode::Char(' ')),
+            RootCommand::NextLayout,
+        ),
+        (
+            KeyBinding::from(KeyCode::Char(';')),
+            RootCommand::FocusPrevious,
+        ),
+        (KeyBinding::from(KeyCode::Char('q')), RootCommand::QuickJump),
+        (
+            KeyBinding::from(KeyCode::Char('{')),
+            RootCommand::Swap(Border::Left),
+        ),
+        (
+            KeyBinding::from(KeyCode::Char('}')),
+            RootCommand::Swap(Border::Right),
+        ),
+        (
+            KeyBinding::from(KeyCode::Char('n')),
+            RootCommand::NextWorkspace(false),
+        ),
+        (
+            KeyBinding::from(KeyCode::Char('p')),
+            RootCommand::NextWorkspace(true),
+        ),
+        (
+            KeyBinding::from(KeyCode::Up),
+            RootCommand::FocusMove(Border::Top),
+        ),
+        (
+            KeyBinding::from(KeyCode::Down),
+            RootCommand::FocusMove(Border::Bottom),
+        ),
+        (
+            KeyBinding::from(KeyCode::Left),
+            RootCommand::FocusMove(Border::Left),
+        ),
+        (
+            KeyBinding::from(KeyCode::Right),
+            RootCommand::FocusMove(Border::Right),
+        ),
+    ]
+    .into_iter()
+    .chain(
+        [
+            (KeyCode::Up, Border::Top),
+            (KeyCode::Down, Border::Bottom),
+            (KeyCode::Left, Border::Left),
+            (KeyCode::Right, Border::Right),
+        ]
+        .into_iter()
+        .flat_map(|(code, border)| {
+            [
+                (
+                    KeyBinding::new(code, KeyModifiers::CONTROL),
+                    RootCommand::Resize(border.clone(), 1),
+                ),
+                (
+                    KeyBinding::new(code, KeyModifiers::ALT),
+                    RootCommand::Resize(border, 5),
+                ),
+            ]
+        }),
+    )
+    .chain((0..10).map(|i| {
+        (
+            KeyBinding::from(KeyCode::Char(char::from(b'0' + i))),
+            RootCommand::Workspace(i as usize),
+        )
+    }))
+    .collect()
+}
+
+/// Returns if the path leads to a component that can take focus
+fn is_focusable(container: &dyn Container, path: &str) -> bool {
+    matches!(
+        container.search_name(path),
+        Some((ContainerChild::Component(component), _)) if component.is_focusable()
+    )
+}
+
+/// Returns the name for a container splitting the named child, adding a
+/// counter if one of the other children already has that name
+fn get_split_name(children: &[ContainerChild], name: &str) -> String {
+    let taken = |split_name: &str| {
+        children
+            .iter()
+            .any(|c| c.as_base().get_name() == split_name)
+    };
+    let mut split_name = format!("{name}_split");
+    let mut count = 1;
+    while taken(&split_name) {
+        count += 1;
+        split_name = format!("{name}_split{count}");
+    }
+    split_name
+}
+
+/// Keymap of vim style window commands, such as `Ctrl-w h` to move focus left
+pub fn vim_window_keymap() -> Keymap<RootCommand> {
+    let ctrl_w = KeyBinding::new(KeyCode::Char('w'), KeyModifiers::CONTROL);
+    [
+        ('h', RootCommand::FocusMove(Border::Left)),
+        ('j', RootCommand::FocusMove(Border::Bottom)),
+        ('k', RootCommand::FocusMove(Border::Top)),
+        ('l', RootCommand::FocusMove(Border::Right)),
+        ('p', RootCommand::FocusPrevious),
+        ('s', RootCommand::Split(Direction::Vertical)),
+        ('v', RootCommand::Split(Direction::Horizontal)),
+        ('c', RootCommand::Close),
+        ('q', RootCommand::Close),
+        ('o', RootCommand::Zoom),
+        ('+', RootCommand::Resize(Border::Bottom, 1)),
+        ('-', RootCommand::Resize(Border::Bottom, -1)),
+        ('>', RootCommand::Resize(Border::Right, 1)),
+        ('<', RootCommand::Resize(Border::Right, -1)),
+        ('=', RootCommand::Equalize(true)),
+        ('r', RootCommand::Rotate(false)),
+        ('R', RootCommand::Rotate(true)),
+    ]
+    .into_iter()
+    .fold(Keymap::new(), |keymap, (c, command)| {
+        keymap.with_binding(
+            vec![ctrl_w.clone(), KeyBinding::from(KeyCode::Char(c))],
+            command,
+        )
+    })
+}
+
+/// Characters used for quick jump labels, in order of assignment
+const QUICK_JUMP_CHARS: &str = "123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Generates a unique label for each of the count components, using two
+/// characters per label when there are too many components for one
+fn quick_jump_labels(count: usize) -> Vec<String> {
+    let chars = QUICK_JUMP_CHARS.chars().collect::<Vec<char>>();
+    if count <= chars.len() {
+        return chars.iter().take(count).map(|c| c.to_string()).collect();
+    }
+    chars
+        .iter()
+        .flat_map(|a| chars.iter().map(move |b| format!("{a}{b}")))
+        .take(count)
+        .collect()
+}
+
+struct QuickJumpLabel {
+    label: String,
+    path: String,
+    rect: Rect,
+}
+
+/// Default context menu items for right clicks on the border of a component
+fn default_border_menu() -> Vec<(String, RootCommand)> {
+    vec![
+        (
+            String::from("Split horizontal"),
+            RootCommand::Split(Direction::Horizontal),
+        ),
+        (
+            String::from("Split vertical"),
+            RootCommand::Split(Direction::Vertical),
+        ),
+        (String::from("Close"), RootCommand::Close),
+        (String::from("Zoom"), RootCommand::Zoom),
+    ]
+}
+
+/// Where the choice of a context menu item is sent
+enum MenuEntry {
+    /// Sent to the widget the menu was opened on
+    Widget(String),
+    /// Returned to the application from a container item
+    Action(String),
+    /// Run by the root on the component the menu was opened on
+    Command(RootCommand),
+}
+
+/// Where a dragged component is dropped relative to the component under the
+/// mouse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DropZone {
+    /// Splits the component, placing the dragged component on this side
+    Edge(Border),
+    /// Swaps the dragged component with the component
+    Center,
+}
+
+/// A component being dragged by its title to a new place in the layout
+struct PaneDrag {
+    x: u16,
+    y: u16,
+    path: String,
+    active: bool,
+    target: Option<(String, DropZone)>,
+}
+
+/// A context menu opened by a right click on a component
+struct ContextMenu {
+    x: u16,
+    y: u16,
+    path: String,
+    entries: Vec<(String, MenuEntry)>,
+    selected: usize,
+}
+
+/// The last mouse down, used to count clicks made in quick succession
+struct Click {
+    time: Instant,
+    x: u16,
+    y: u16,
+    button: MouseButton,
+    count: u8,
+}
+
+/// Labels shown over every component while waiting for the user to type one
+struct QuickJump {
+    labels: Vec<QuickJumpLabel>,
+    typed: String,
+}
+
+/// A layout tree kept by the root while another workspace is shown, along
+/// with the focus history and zoomed component it had
+struct Workspace {
+    name: String,
+    container: Option<Box<dyn Container>>,
+    focus_history: Vec<(String, Focus)>,
+    zoomed: Option<String>,
+}
+
+/// The root of a layout tree, directs input to the container it holds and
+/// keeps track of application wide state such as the focus history
+pub struct Root {
+    container: Box<dyn Container>,
+    focus_history: Vec<(String, Focus)>,
+    focus_previous_key: Option<KeyBinding>,
+    quick_jump_key: Option<KeyBinding>,
+    quick_jump: Option<QuickJump>,
+    prefix_key: Option<KeyBinding>,
+    prefix_timeout: Duration,
+    prefix_bindings: Vec<(KeyBinding, RootCommand)>,
+    prefix_pending: Option<Instant>,
+    split_factory: Option<SplitFactory>,
+    zoomed: Option<(String, u16, u16)>,
+    show_status: bool,
+    keymap: Keymap<RootCommand>,
+    navigation_keymap: Keymap<RootCommand>,
+    hotkeys: Vec<Hotkey>,
+    click_last: Option<Click>,
+    click_timeout: Duration,
+    click_distance: u16,
+    title_double_click: Option<RootCommand>,
+    border_menu: Vec<(String, RootCommand)>,
+    menu: Option<ContextMenu>,
+    menu_result: Option<EventResult>,
+    focus_policy: Option<FocusPolicy>,
+    resize_mode: bool,
+    resize_step: u16,
+    drag_panes: bool,
+    pane_drag: Option<PaneDrag>,
+    workspaces: Vec<Workspace>,
+    workspace: usize,
+    show_workspaces: bool,
+    update_inactive: bool,
+}
+
+impl Root {
+    /// Creates a root with the container as its first workspace, named after
+    /// the container
+    pub fn new(container: Box<dyn Container>) -> Self {
+        let workspace = Workspace {
+            name: container.get_name(),
+            container: None,
+            focus_history: Vec::new(),
+            zoomed: None,
+        };
+        Self {
+            container,
+            focus_history: Vec::new(),
+            focus_previous_key: None,
+            quick_jump_key: None,
+            quick_jump: None,
+            prefix_key: None,
+            prefix_timeout: Duration::from_secs(2),
+            prefix_bindings: default_prefix_bindings(),
+            prefix_pending: None,
+            split_factory: None,
+            zoomed: None,
+            show_status: true,
+            keymap: Keymap::new(),
+            navigation_keymap: Keymap::new(),
+            hotkeys: Vec::new(),
+            click_last: None,
+            click_timeout: Duration::from_millis(500),
+            click_distance: 1,
+            title_double_click: Some(RootCommand::Zoom),
+            border_menu: default_border_menu(),
+            menu: None,
+            menu_result: None,
+            focus_policy: None,
+            resize_mode: false,
+            resize_step: 1,
+            drag_panes: false,
+            pane_drag: None,
+            workspaces: vec![workspace],
+            workspace: 0,
+            show_workspaces: false,
+            update_inactive: true,
+        }
+    }
+
+    pub fn get_container(&self) -> &dyn Container {
+        self.container.as_container()
+    }
+
+    pub fn get_container_mut(&mut self) -> &mut dyn Container {
+        self.container.as_container_mut()
+    }
+
+    pub fn get_focus_previous_key(&self) -> Option<KeyBinding> {
+        self.focus_previous_key.clone()
+    }
+
+    pub fn set_focus_previous_key(&mut self, focus_previous_key: Option<KeyBinding>) {
+        self.focus_previous_key = focus_previous_key;
+    }
+
+    pub fn focus_previous_key(mut self, focus_previous_key: Option<KeyBinding>) -> Self {
+        self.set_focus_previous_key(focus_previous_key);
+        self
+    }
+
+    pub fn get_quick_jump_key(&self) -> Option<KeyBinding> {
+        self.quick_jump_key.clone()
+    }
+
+    pub fn set_quick_jump_key(&mut self, quick_jump_key: Option<KeyBinding>) {
+        self.quick_jump_key = quick_jump_key;
+    }
+
+    pub fn quick_jump_key(mut self, quick_jump_key: Option<KeyBinding>) -> Self {
+        self.set_quick_jump_key(quick_jump_key);
+        self
+    }
+
+    pub fn get_prefix_key(&self) -> Option<KeyBinding> {
+        self.prefix_key.clone()
+    }
+
+    /// Sets the key that causes the next key to be read as a layout command
+    /// instead of being sent to the focused component
+    pub fn set_prefix_key(&mut self, prefix_key: Option<KeyBinding>) {
+        self.prefix_key = prefix_key;
+    }
+
+    pub fn prefix_key(mut self, prefix_key: Option<KeyBinding>) -> Self {
+        self.set_prefix_key(prefix_key);
+        self
+    }
+
+    pub fn get_prefix_timeout(&self) -> Duration {
+        self.prefix_timeout
+    }
+
+    /// Sets how long to wait for a layout command after the prefix key
+    pub fn set_prefix_timeout(&mut self, prefix_timeout: Duration) {
+        self.prefix_timeout = prefix_timeout;
+    }
+
+    pub fn prefix_timeout(mut self, prefix_timeout: Duration) -> Self {
+        self.set_prefix_timeout(prefix_timeout);
+        self
+    }
+
+    pub fn get_prefix_bindings(&self) -> &Vec<(KeyBinding, RootCommand)> {
+        &self.prefix_bindings
+    }
+
+    /// Binds a key following the prefix key to a command, replacing any
+    /// existing binding for the key
+    pub fn bind_prefix(&mut self, key: KeyBinding, command: RootCommand) {
+        self.unbind_prefix(&key);
+        self.prefix_bindings.push((key, command));
+    }
+
+    pub fn unbind_prefix(&mut self, key: &KeyBinding) {
+        self.prefix_bindings.retain(|(k, _)| k != key);
+    }
+
+    pub fn is_prefix_pending(&self) -> bool {
+        self.prefix_pending.is_some()
+    }
+
+    /// Sets the factory used to create new components when splitting
+    pub fn set_split_factory(&mut self, split_factory: Option<SplitFactory>) {
+        self.split_factory = split_factory;
+    }
+
+    pub fn split_factory(mut self, split_factory: Option<SplitFactory>) -> Self {
+        self.set_split_factory(split_factory);
+        self
+    }
+
+    pub fn get_click_timeout(&self) -> Duration {
+        self.click_timeout
+    }
+
+    /// Sets how long after a click the next click counts towards a double or
+    /// triple click
+    pub fn set_click_timeout(&mut self, click_timeout: Duration) {
+        self.click_timeout = click_timeout;
+    }
+
+    pub fn click_timeout(mut self, click_timeout: Duration) -> Self {
+        self.set_click_timeout(click_timeout);
+        self
+    }
+
+    pub fn get_click_distance(&self) -> u16 {
+        self.click_distance
+    }
+
+    /// Sets how many cells the mouse can move between clicks and still count
+    /// towards a double or triple click
+    pub fn set_click_distance(&mut self, click_distance: u16) {
+        self.click_distance = click_distance;
+    }
+
+    pub fn click_distance(mut self, click_distance: u16) -> Self {
+        self.set_click_distance(click_distance);
+        self
+    }
+
+    pub fn get_title_double_click(&self) -> Option<RootCommand> {
+        self.title_double_click.clone()
+    }
+
+    /// Sets the command run when double clicking the title of a component
+    pub fn set_title_double_click(&mut self, title_double_click: Option<RootCommand>) {
+        self.title_double_click = title_double_click;
+    }
+
+    pub fn title_double_click(mut self, title_double_click: Option<RootCommand>) -> Self {
+        self.set_title_double_click(title_double_click);
+        self
+    }
+
+    pub fn get_focus_policy(&self) -> Option<FocusPolicy> {
+        self.focus_policy.clone()
+    }
+
+    /// Sets how the mouse gives focus to every component, including those
+    /// added later by splitting, or leaves each component to its own policy
+    pub fn set_focus_policy(&mut self, focus_policy: Option<FocusPolicy>) {
+        self.focus_policy = focus_policy;
+        let Some(focus_policy) = self.focus_policy.clone() else {
+            return;
+        };
+        let paths: Vec<String> = self
+            .get_container()
+            .search_components()
+            .into_iter()
+            .map(|(path, _, _)| path)
+            .collect();
+        for path in paths {
+            if let Some((ContainerChild::Component(component), _)) =
+                self.get_container_mut().search_name_mut(&path)
+            {
+                component.set_focus_policy(focus_policy.clone());
+            }
+        }
+    }
+
+    pub fn focus_policy(mut self, focus_policy: Option<FocusPolicy>) -> Self {
+        self.set_focus_policy(focus_policy);
+        self
+    }
+
+    pub fn get_border_menu(&self) -> &Vec<(String, RootCommand)> {
+        &self.border_menu
+    }
+
+    /// Sets the context menu items added for right clicks on the border of a
+    /// component, which run the command on that component
+    pub fn set_border_menu(&mut self, border_menu: Vec<(String, RootCommand)>) {
+        self.border_menu = border_menu;
+    }
+
+    pub fn border_menu(mut self, border_menu: Vec<(String, RootCommand)>) -> Self {
+        self.set_border_menu(border_menu);
+        self
+    }
+
+    /// Opens the context menu for the component at the position, asking the
+    /// widget and then each enclosing container for items, returning false if
+    /// there were no items
+    pub fn open_menu(&mut self, x: u16, y: u16) -> bool {
+        self.close_menu();
+        // The zoomed component covers the whole root
+        let found = match &self.zoomed {
+            Some((path, _, _)) => Some((path.clone(), (x, y))),
+            None => self
+                .get_component_at(x, y)
+                .map(|(path, _, local)| (path, local)),
+        };
+        let Some((path, (x_local, y_local))) = found else {
+            return false;
+        };
+        let Some((ContainerChild::Component(component), _)) =
+            self.get_container().search_name(&path)
+        else {
+            return false;
+        };
+        let on_border = component.get_border(x_local, y_local).is_some();
+        let mut entries: Vec<(String, MenuEntry)> = component
+            .get_menu_items(x_local, y_local)
+            .into_iter()
+            .map(|item| (item.label, MenuEntry::Widget(item.action)))
+            .collect();
+        // Add the items of each enclosing container, innermost first
+        let mut parents: Vec<&str> = path.match_indices('.').map(|(i, _)| &path[..i]).collect();
+        parents.reverse();
+        let container_items = |items: Vec<MenuItem>| {
+            items
+                .into_iter()
+                .map(|item| (item.label, MenuEntry::Action(item.action)))
+        };
+        for parent in parents {
+            if let Some((ContainerChild::Container(parent), _)) =
+                self.get_container().search_name(parent)
+            {
+                entries.extend(container_items(parent.get_menu_items()));
+            }
+        }
+        entries.extend(container_items(self.get_container().get_menu_items()));
+        if on_border {
+            entries.extend(
+                self.border_menu
+                    .iter()
+                    .map(|(label, command)| (label.clone(), MenuEntry::Command(command.clone()))),
+            );
+        }
+        if entries.is_empty() {
+            return false;
+        }
+        self.menu = Some(ContextMenu {
+            x,
+            y,
+            path,
+            entries,
+            selected: 0,
+        });
+        true
+    }
+
+    pub fn close_menu(&mut self) {
+        if self.menu.take().is_some() {
+            self.invalidate();
+        }
+    }
+
+    /// Returns the labels of the open context menu and which one is selected
+    pub fn get_menu(&self) -> Option<(Vec<String>, usize)> {
+        let menu = self.menu.as_ref()?;
+        let labels = menu
+            .entries
+            .iter()
+            .map(|(label, _)| label.clone())
+            .collect();
+        Some((labels, menu.selected))
+    }
+
+    /// Returns the result of the context menu item last chosen with the
+    /// mouse, for applications passing mouse events to `handle_mouse`
+    /// directly instead of through `handle_input`
+    pub fn take_menu_result(&mut self) -> Option<EventResult> {
+        self.menu_result.take()
+    }
+
+    /// Chooses the item of the open context menu, closing it
+    pub fn select_menu_item(&mut self, index: usize) -> EventResult {
+        let Some(menu) = self.menu.take() else {
+            return EventResult::Ignored;
+        };
+        self.invalidate();
+        let Some((_, entry)) = menu.entries.into_iter().nth(index) else {
+            return EventResult::Redraw;
+        };
+        match entry {
+            MenuEntry::Widget(action) => match self.get_container_mut().search_name_mut(&menu.path)
+            {
+                Some((ContainerChild::Component(component), _)) => component.handle_menu(&action),
+                _ => EventResult::Ignored,
+            },
+            MenuEntry::Action(action) => EventResult::Action(action),
+            MenuEntry::Command(command) => {
+                // Commands act on the focused component, so focus it first
+                if self.get_container().search_focused_name().as_ref() != Some(&menu.path) {
+                    self.focus_name(&menu.path, Focus::PartialFocus);
+                }
+                self.run_command(command);
+                EventResult::Redraw
+            }
+        }
+    }
+
+    fn handle_menu_key(&mut self, e: KeyEvent) -> EventResult {
+        let Some(menu) = &mut self.menu else {
+            return EventResult::Ignored;
+        };
+        let count = menu.entries.len();
+        match e.code {
+            KeyCode::Up => menu.selected = (menu.selected + count - 1) % count,
+            KeyCode::Down => menu.selected = (menu.selected + 1) % count,
+            KeyCode::Enter => {
+                let selected = menu.selected;
+                return self.select_menu_item(selected);
+            }
+            KeyCode::Esc => self.close_menu(),
+            _ => {}
+        }
+        EventResult::Redraw
+    }
+
+    /// Handles a mouse event while the context menu is open, returning false
+    /// if the event should still be handled normally
+    fn handle_menu_mouse(&mut self, x: u16, y: u16, kind: Option<MouseEventKind>) -> bool {
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: self.get_width(),
+            height: self.get_height(),
+        };
+        let (Some(rect), Some(menu)) = (self.get_menu_rect(area), &mut self.menu) else {
+            return false;
+        };
+        let inside = ComponentPos { x, y }.intersects_rect(rect);
+        // Rows past the last entry can only show up if the menu was clipped
+        let last = menu.entries.len().saturating_sub(1);
+        let index = (inside && y > rect.y && y < rect.y + rect.height - 1)
+            .then(|| std::cmp::min((y - rect.y - 1) as usize, last));
+        match (kind, index) {
+            (Some(MouseEventKind::Moved), Some(index)) => menu.selected = index,
+            (Some(MouseEventKind::Down(MouseButton::Left)), Some(index)) => {
+                let result = self.select_menu_item(index);
+                self.menu_result = Some(result);
+            }
+            (Some(MouseEventKind::Down(button)), _) if !inside => {
+                self.close_menu();
+                // Right clicking elsewhere opens a new menu there
+                return button != MouseButton::Right;
+            }
+            (None, _) => {
+                self.close_menu();
+                return false;
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Returns where the context menu is drawn, next to where it was opened
+    /// and moved to fit in the area, clipped to the area if it is too small
+    fn get_menu_rect(&self, area: Rect) -> Option<Rect> {
+        let menu = self.menu.as_ref()?;
+        let label_width = menu
+            .entries
+            .iter()
+            .map(|(label, _)| label.chars().count())
+            .max()
+            .unwrap_or_default() as u16;
+        let width = std::cmp::min(label_width + 4, area.width);
+        let height = std::cmp::min(menu.entries.len() as u16 + 2, area.height);
+        let rect = Rect {
+            x: area.x + std::cmp::min(menu.x, area.width - width),
+            y: area.y + std::cmp::min(menu.y, area.height - height),
+            width,
+            height,
+        };
+        clip_rect(rect, area)
+    }
+
+    fn render_menu(&self, area: Rect, buf: &mut Buffer) {
+        let (Some(rect), Some(menu)) = (self.get_menu_rect(area), &self.menu) else {
+            return;
+        };
+        let style = Style::default().fg(Color::White).bg(Color::Black);
+        Clear.render(rect, buf);
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .style(style)
+            .render(rect, buf);
+        if rect.width <= 2 {
+            return;
+        }
+        let rows = rect.height.saturating_sub(2) as usize;
+        for (i, (label, _)) in menu.entries.iter().take(rows).enumerate() {
+            let style = if i == menu.selected {
+                style.add_modifier(Modifier::REVERSED)
+            } else {
+                style
+            };
+            let width = (rect.width - 2) as usize;
+            let text = format!(" {label:<width$}");
+            buf.set_stringn(rect.x + 1, rect.y + 1 + i as u16, text, width, style);
+        }
+    }
+
+    /// Counts the clicks made in quick succession ending with this one
+    fn count_click(&mut self, x: u16, y: u16, button: MouseButton) -> u8 {
+        let count = match &self.click_last {
+            Some(last)
+                if last.button == button
+                    && last.time.elapsed() <= self.click_timeout
+                    && last.x.abs_diff(x) <= self.click_distance
+                    && last.y.abs_diff(y) <= self.click_distance =>
+            {
+                last.count.saturating_add(1)
+            }
+            _ => 1,
+        };
+        self.click_last = Some(Click {
+            time: Instant::now(),
+            x,
+            y,
+            button,
+            count,
+        });
+        count
+    }
+
+    /// Returns the path of the component shown at the position, the component
+    /// and the position in the coordinates of the component
+    fn get_component_at(&self, x: u16, y: u16) -> Option<(String, &Component, (u16, u16))> {
+        self.get_container()
+            .search_component_views()
+            .into_iter()
+            .find(|(_, _, pos, part)| {
+                ComponentPos { x, y }.intersects_rect(Rect {
+                    x: pos.x,
+                    y: pos.y,
+                    ..*part
+                })
+            })
+            .map(|(path, component, pos, part)| {
+                (path, component, (x - pos.x + part.x, y - pos.y + part.y))
+            })
+    }
+
+    /// Returns if the position is on the title bar of a component
+    fn is_title(&self, x: u16, y: u16) -> bool {
+        // The zoomed component covers the whole root
+        let title = match &self.zoomed {
+            Some((path, _, _)) => match self.get_container().search_name(path) {
+                Some((ContainerChild::Component(component), _)) => Some((component, y)),
+                _ => None,
+            },
+            None => self
+                .get_component_at(x, y)
+                .map(|(_, component, (_, y_local))| (component, y_local)),
+        };
+        matches!(title, Some((component, 0)) if component.get_border_width() > 0)
+    }
+
+    pub fn get_show_status(&self) -> bool {
+        self.show_status
+    }
+
+    /// Sets if the status is drawn in the bottom right corner of the root
+    pub fn set_show_status(&mut self, show_status: bool) {
+        self.show_status = show_status;
+        self.invalidate();
+    }
+
+    pub fn show_status(mut self, show_status: bool) -> Self {
+        self.set_show_status(show_status);
+        self
+    }
+
+    pub fn get_keymap(&self) -> &Keymap<RootCommand> {
+        &self.keymap
+    }
+
+    pub fn get_keymap_mut(&mut self) -> &mut Keymap<RootCommand> {
+        &mut self.keymap
+    }
+
+    /// Sets the keymap checked for every key before it is sent on to the
+    /// focused component
+    pub fn set_keymap(&mut self, keymap: Keymap<RootCommand>) {
+        self.keymap = keymap;
+    }
+
+    pub fn keymap(mut self, keymap: Keymap<RootCommand>) -> Self {
+        self.set_keymap(keymap);
+        self
+    }
+
+    pub fn get_navigation_keymap(&self) -> &Keymap<RootCommand> {
+        &self.navigation_keymap
+    }
+
+    pub fn get_navigation_keymap_mut(&mut self) -> &mut Keymap<RootCommand> {
+        &mut self.navigation_keymap
+    }
+
+    /// Sets the keymap checked for keys only while no component has full
+    /// focus, such as when moving between components with partial focus
+    pub fn set_navigation_keymap(&mut self, navigation_keymap: Keymap<RootCommand>) {
+        self.navigation_keymap = navigation_keymap;
+    }
+
+    pub fn navigation_keymap(mut self, navigation_keymap: Keymap<RootCommand>) -> Self {
+        self.set_navigation_keymap(navigation_keymap);
+        self
+    }
+
+    /// Registers an application wide hotkey, replacing any existing hotkey
+    /// for the same key
+    pub fn add_hotkey(&mut self, key: KeyBinding, priority: HotkeyPriority, action: HotkeyAction) {
+        self.remove_hotkey(&key);
+        self.hotkeys.push(Hotkey {
+            key,
+            priority,
+            action,
+        });
+    }
+
+    pub fn hotkey(
+        mut self,
+        key: KeyBinding,
+        priority: HotkeyPriority,
+        action: HotkeyAction,
+    ) -> Self {
+        self.add_hotkey(key, priority, action);
+        self
+    }
+
+    pub fn remove_hotkey(&mut self, key: &KeyBinding) {
+        self.hotkeys.retain(|h| h.key != *key);
+    }
+
+    /// Runs the hotkey matching the key and priority, if there is one
+    fn handle_hotkey(&mut self, e: KeyEvent, priority: HotkeyPriority) -> Option<EventResult> {
+        let hotkey = self
+            .hotkeys
+            .iter_mut()
+            .find(|h| h.priority == priority && h.key.matches(&e))?;
+        Some(match &mut hotkey.action {
+            HotkeyAction::Action(action) => EventResult::Action(action.clone()),
+            HotkeyAction::Callback(callback) => callback(e),
+        })
+    }
+
+    /// Returns the remaining keys and commands of the bindings that could
+    /// still be completed from the keys pressed so far
+    pub fn get_completions(&self) -> Vec<(Vec<KeyBinding>, RootCommand)> {
+        let mut completions = self.keymap.get_completions();
+        completions.extend(self.navigation_keymap.get_completions());
+        completions
+    }
+
+    /// Returns a short description of any pending input state, such as
+    /// waiting for a layout command after the prefix key
+    pub fn get_status(&self) -> Option<String> {
+        let pending = if self.keymap.is_pending() {
+            self.keymap.get_pending()
+        } else {
+            self.navigation_keymap.get_pending()
+        };
+        if self.quick_jump.is_some() {
+            Some(String::from("JUMP"))
+        } else if self.resize_mode {
+            Some(String::from("RESIZE"))
+        } else if self.prefix_pending.is_some() {
+            Some(String::from("PREFIX"))
+        } else if !pending.is_empty() {
+            let keys = pending
+                .iter()
+                .map(|e| KeyBinding::from(*e).to_string())
+                .collect::<Vec<String>>();
+            Some(keys.join(" "))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_resize_step(&self) -> u16 {
+        self.resize_step
+    }
+
+    /// Sets how many cells each arrow key moves a border in resize mode
+    pub fn set_resize_step(&mut self, resize_step: u16) {
+        self.resize_step = resize_step;
+    }
+
+    pub fn resize_step(mut self, resize_step: u16) -> Self {
+        self.set_resize_step(resize_step);
+        self
+    }
+
+    pub fn get_drag_panes(&self) -> bool {
+        self.drag_panes
+    }
+
+    /// Sets if components can be dragged by the text of their title and
+    /// dropped on another component to rearrange the layout, off by default
+    pub fn set_drag_panes(&mut self, drag_panes: bool) {
+        self.drag_panes = drag_panes;
+        if !drag_panes {
+            self.pane_drag = None;
+        }
+    }
+
+    pub fn drag_panes(mut self, drag_panes: bool) -> Self {
+        self.set_drag_panes(drag_panes);
+        self
+    }
+
+    pub fn get_show_workspaces(&self) -> bool {
+        self.show_workspaces
+    }
+
+    /// Sets if a bar listing the workspaces is drawn along the bottom of the
+    /// root, taking a row from the active workspace
+    pub fn set_show_workspaces(&mut self, show_workspaces: bool) -> Result<(), ResizeError> {
+        let (width, height) = (self.get_width(), self.get_height());
+        let sized = self.container.get_width() > 0 || self.container.get_height() > 0;
+        let previous = std::mem::replace(&mut self.show_workspaces, show_workspaces);
+        if sized {
+            if let Err(err) = self.resize(width, height) {
+                self.show_workspaces = previous;
+                return Err(err);
+            }
+        }
+        self.invalidate();
+        Ok(())
+    }
+
+    pub fn show_workspaces(mut self, show_workspaces: bool) -> Result<Self, ResizeError> {
+        self.set_show_workspaces(show_workspaces)?;
+        Ok(self)
+    }
+
+    pub fn get_update_inactive(&self) -> bool {
+        self.update_inactive
+    }
+
+    /// Sets if the workspaces that are not shown still get updates, otherwise
+    /// only the active workspace is updated
+    pub fn set_update_inactive(&mut self, update_inactive: bool) {
+        self.update_inactive = update_inactive;
+    }
+
+    pub fn update_inactive(mut self, update_inactive: bool) -> Self {
+        self.set_update_inactive(update_inactive);
+        self
+    }
+
+    /// Adds a workspace holding the container after the existing ones,
+    /// returning its index
+    pub fn add_workspace(&mut self, name: &str, container: Box<dyn Container>) -> usize {
+        self.workspaces.push(Workspace {
+            name: name.to_string(),
+            container: Some(container),
+            focus_history: Vec::new(),
+            zoomed: None,
+        });
+        self.invalidate();
+        self.workspaces.len() - 1
+    }
+
+    pub fn workspace(mut self, name: &str, container: Box<dyn Container>) -> Self {
+        self.add_workspace(name, container);
+        self
+    }
+
+    /// Removes the workspace at the index and returns its container, switching
+    /// to the next workspace first if it is active, and returns none if it is
+    /// the only workspace or the next could not be shown
+    pub fn remove_workspace(&mut self, index: usize) -> Option<Box<dyn Container>> {
+        if index >= self.workspaces.len() || self.workspaces.len() < 2 {
+            return None;
+        }
+        if index == self.workspace {
+            let next = match index + 1 < self.workspaces.len() {
+                true => index + 1,
+                false => index - 1,
+            };
+            if !self.switch_workspace(next) {
+                return None;
+            }
+        }
+        let workspace = self.workspaces.remove(index);
+        if index < self.workspace {
+            self.workspace -= 1;
+        }
+        self.invalidate();
+        workspace.container
+    }
+
+    /// Returns the names of the workspaces in order
+    pub fn get_workspaces(&self) -> Vec<String> {
+        self.workspaces.iter().map(|w| w.name.clone()).collect()
+    }
+
+    /// Returns the index of the active workspace
+    pub fn get_workspace(&self) -> usize {
+        self.workspace
+    }
+
+    /// Returns the container of the workspace at the index, whether it is
+    /// active or not
+    pub fn get_workspace_container(&self, index: usize) -> Option<&dyn Container> {
+        match index == self.workspace {
+            true => Some(self.get_container()),
+            false => Some(
+                self.workspaces
+                    .get(index)?
+                    .container
+                    .as_ref()?
+                    .as_container(),
+            ),
+        }
+    }
+
+    pub fn get_workspace_container_mut(&mut self, index: usize) -> Option<&mut dyn Container> {
+        match index == self.workspace {
+            true => Some(self.get_container_mut()),
+            false => Some(
+                self.workspaces
+                    .get_mut(index)?
+                    .container
+                    .as_mut()?
+                    .as_container_mut(),
+            ),
+        }
+    }
+
+    /// Shows the workspace at the index instead of the active one, keeping
+    /// the focus and zoom of both, and returns false if there is no such
+    /// workspace or it could not be resized to fit the root
+    pub fn switch_workspace(&mut self, index: usize) -> bool {
+        if index == self.workspace || index >= self.workspaces.len() {
+            return false;
+        }
+        let (width, height) = (self.container.get_width(), self.container.get_height());
+        let Some(mut container) = self.workspaces[index].container.take() else {
+            return false;
+        };
+        if (width > 0 || height > 0) && container.resize(width, height).is_err() {
+            self.workspaces[index].container = Some(container);
+            return false;
+        }
+        self.stop_quick_jump();
+        self.close_menu();
+        self.stop_resize_mode();
+        self.pane_drag = None;
+        self.container.handle_mouse_leave();
+        let zoomed = self.get_zoomed();
+        self.unzoom();
+        let previous = std::mem::replace(&mut self.container, container);
+        let current = &mut self.workspaces[self.workspace];
+        current.container = Some(previous);
+        current.focus_history = std::mem::take(&mut self.focus_history);
+        current.zoomed = zoomed;
+        self.workspace = index;
+        self.focus_history = std::mem::take(&mut self.workspaces[index].focus_history);
+        if let Some(path) = self.workspaces[index].zoomed.take() {
+            if self.focus_name(&path, self.get_container().get_focus()) {
+                self.toggle_zoom();
+            }
+        }
+        self.invalidate();
+        true
+    }
+
+    /// Shows the workspace with the matching name, returning false if there
+    /// is no such workspace or it could not be shown
+    pub fn switch_workspace_name(&mut self, name: &str) -> bool {
+        match self.workspaces.iter().position(|w| w.name == name) {
+            Some(index) => self.switch_workspace(index),
+            None => false,
+        }
+    }
+
+    /// Shows the next workspace, or the previous if reversed, wrapping around
+    /// at either end
+    pub fn next_workspace(&mut self, reverse: bool) -> bool {
+        let count = self.workspaces.len();
+        let index = match reverse {
+            true => (self.workspace + count - 1) % count,
+            false => (self.workspace + 1) % count,
+        };
+        self.switch_workspace(index)
+    }
+
+    /// Returns the number of rows taken by the workspace bar
+    fn get_workspace_bar_height(&self) -> u16 {
+        self.show_workspaces as u16
+    }
+
+    /// Returns the label of each workspace in the bar and where it starts
+    fn get_workspace_labels(&self) -> Vec<(u16, String)> {
+        let mut x = 0;
+        self.workspaces
+            .iter()
+            .enumerate()
+            .map(|(i, workspace)| {
+                let label = format!(" {}:{} ", i, workspace.name);
+                x += label.chars().count() as u16;
+                (x - label.chars().count() as u16, label)
+            })
+            .collect()
+    }
+
+    /// Switches to the workspace with its label under the mouse on the bar,
+    /// returning true if the mouse was on the bar
+    fn handle_workspace_bar_mouse(&mut self, x: u16, y: u16, kind: Option<MouseEventKind>) -> bool {
+        if !self.show_workspaces || y != self.container.get_height() {
+            return false;
+        }
+        if let Some(MouseEventKind::Down(MouseButton::Left)) = kind {
+            let index = self
+                .get_workspace_labels()
+                .iter()
+                .position(|(start, label)| x >= *start && x < start + label.chars().count() as u16);
+            if let Some(index) = index {
+                self.switch_workspace(index);
+            }
+        }
+        true
+    }
+
+    /// Draws the workspace bar in the bottom row, highlighting the active
+    /// workspace
+    fn render_workspace_bar(&self, area: Rect, buf: &mut Buffer) {
+        if !self.show_workspaces || area.height == 0 {
+            return;
+        }
+        let y = area.y + area.height - 1;
+        let bar = Rect {
+            y,
+            height: 1,
+            ..area
+        };
+        buf.set_style(bar, Style::default().fg(Color::White).bg(Color::DarkGray));
+        for x in bar.left()..bar.right() {
+            buf.get_mut(x, y).set_symbol(" ");
+        }
+        for (i, (start, label)) in self.get_workspace_labels().into_iter().enumerate() {
+            if start >= area.width {
+                break;
+            }
+            let style = match i == self.workspace {
+                true => Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+                false => Style::default().fg(Color::White).bg(Color::DarkGray),
+            };
+            let width = (area.width - start) as usize;
+            buf.set_stringn(area.x + start, y, label, width, style);
+        }
+    }
+
+    /// Grows the focused component by moving its border outwards by the delta
+    /// (or inwards if negative), using the opposite border if there is nothing
+    /// across the first one
+    pub fn resize_focused(&mut self, border: Border, delta: i16) -> bool {
+        self.unzoom();
+        let Some(path) = self.get_container().search_focused_name() else {
+            return false;
+        };
+        self.resize_border(&path, border.clone(), delta)
+            || self.resize_border(&path, border.opposite(), delta)
+    }
+
+    /// Moves the border of the child at the path outwards by the delta, using
+    /// the divider of the nearest ancestor when the border is on the outside
+    /// of the parent
+    fn resize_border(&mut self, path: &str, border: Border, delta: i16) -> bool {
+        let Some((parent, name)) = self.search_parent_mut(path) else {
+            return false;
+        };
+        let rects = parent.get_children_rectangles();
+        let Some(rect) = parent
+            .get_children()
+            .iter()
+            .position(|c| c.as_base().get_name() == name)
+            .map(|i| rects[i])
+        else {
+            return false;
+        };
+        let (width, height) = (parent.as_base().get_width(), parent.as_base().get_height());
+        let outer = match border {
+            Border::Left => rect.x == 0,
+            Border::Top => rect.y == 0,
+            Border::Right => rect.x + rect.width >= width,
+            Border::Bottom => rect.y + rect.height >= height,
+        };
+        if !outer {
+            return parent.resize_child(&name, border, delta);
+        }
+        match path.rsplit_once('.') {
+            Some((parent_path, _)) => self.resize_border(parent_path, border, delta),
+            None => false,
+        }
+    }
+
+    /// Returns the container holding the focused component, or the top level
+    /// container if nothing has focus
+    fn get_focused_parent_mut(&mut self) -> Option<&mut dyn Container> {
+        let path = self.get_container().search_focused_name();
+        match path.as_deref().and_then(|path| path.rsplit_once('.')) {
+            Some((parent, _)) => match self.get_container_mut().search_name_mut(parent) {
+                Some((ContainerChild::Container(parent), _)) => Some(parent.as_container_mut()),
+                _ => None,
+            },
+            None => Some(self.get_container_mut()),
+        }
+    }
+
+    /// Sets the children of the container holding the focused component to
+    /// the same size, including the children of nested containers if recursive
+    pub fn equalize(&mut self, recursive: bool) -> bool {
+        self.unzoom();
+        self.get_focused_parent_mut()
+            .is_some_and(|parent| parent.equalize(recursive).is_ok())
+    }
+
+    /// Moves the children of the container holding the focused component to
+    /// the next position, or the previous if reversed
+    pub fn rotate(&mut self, reverse: bool) -> bool {
+        self.unzoom();
+        self.get_focused_parent_mut()
+            .is_some_and(|parent| parent.rotate(reverse).is_ok())
+    }
+
+    /// Switches the orientation of the container holding the focused component
+    pub fn flip(&mut self) -> bool {
+        self.unzoom();
+        self.get_focused_parent_mut()
+            .is_some_and(|parent| parent.flip().is_ok())
+    }
+
+    /// Switches the container holding the focused component to its next
+    /// layout
+    pub fn next_layout(&mut self) -> bool {
+        self.unzoom();
+        self.get_focused_parent_mut()
+            .is_some_and(|parent| parent.next_layout().is_ok())
+    }
+
+    /// Swaps the focused component with the next component across the border,
+    /// each taking the size of the other, and returns false if there is no
+    /// such component or either could not be resized
+    pub fn swap_focused(&mut self, border: Border) -> bool {
+        self.unzoom();
+        let (pos, size) = match self.get_container().search_focused() {
+            FocusResult::Focus((component, pos)) | FocusResult::PartialFocus((component, pos)) => {
+                (pos, (component.get_width(), component.get_height()))
+            }
+            FocusResult::None => return false,
+        };
+        let container = self.get_container();
+        let Some((start, _, _)) = get_focused_position(container) else {
+            return false;
+        };
+        let Some(next_pos) = find_next_pos(container, start, border, size.0, size.1) else {
+            return false;
+        };
+        let Some((next, next_pos)) = container.search_position(next_pos) else {
+            return false;
+        };
+        let next_size = (next.get_width(), next.get_height());
+        self.swap_components((pos, size), (next_pos, next_size))
+    }
+
+    /// Swaps the components at the positions, each taking the size of the
+    /// other, and returns false if either could not be resized
+    fn swap_components(
+        &mut self,
+        (pos, size): (ComponentPos, (u16, u16)),
+        (next_pos, next_size): (ComponentPos, (u16, u16)),
+    ) -> bool {
+        // Take both out, leaving placeholders of the same size so the
+        // positions of the components do not change
+        let Some(component) = self.replace_component(pos.clone(), size) else {
+            return false;
+        };
+        let Some(next) = self.replace_component(next_pos.clone(), next_size) else {
+            self.put_component(pos, component);
+            return false;
+        };
+        let (component, next, swapped) = Root::swap_sizes(component, next, size, next_size);
+        let (first, second) = if swapped {
+            (next, component)
+        } else {
+            (component, next)
+        };
+        self.put_component(pos, first);
+        self.put_component(next_pos, second);
+        self.invalidate();
+        swapped
+    }
+
+    /// Moves the component at the path next to the target component, splitting
+    /// the target along the edge of the zone or swapping the two for the
+    /// center, and returns false without changes if there is no room
+    pub fn move_pane(&mut self, path: &str, target: &str, zone: DropZone) -> bool {
+        self.unzoom();
+        let container = self.get_container();
+        let (Some((ContainerChild::Component(component), pos)), Some((target_child, target_pos))) =
+            (container.search_name(path), container.search_name(target))
+        else {
+            return false;
+        };
+        let ContainerChild::Component(target_component) = target_child else {
+            return false;
+        };
+        let size = (component.get_width(), component.get_height());
+        let target_size = (target_component.get_width(), target_component.get_height());
+        let border = match zone {
+            _ if path == target => return false,
+            DropZone::Center => {
+                return self.swap_components((pos, size), (target_pos, target_size))
+            }
+            DropZone::Edge(border) => border,
+        };
+        let direction = match border {
+            Border::Left | Border::Right => Direction::Horizontal,
+            Border::Top | Border::Bottom => Direction::Vertical,
+        };
+        // Check both fit in the space of the target before moving anything,
+        // as the target only grows when the component is taken out, which a
+        // component with a fixed size across the split could not follow
+        let along = |size: (u16, u16)| match direction {
+            Direction::Horizontal => size.0,
+            Direction::Vertical => size.1,
+        };
+        let across = |size: (u16, u16)| match direction {
+            Direction::Horizontal => size.1,
+            Direction::Vertical => size.0,
+        };
+        let opposite = match direction {
+            Direction::Horizontal => Direction::Vertical,
+            Direction::Vertical => Direction::Horizontal,
+        };
+        let (min, _) = component.get_size_range(direction.clone());
+        let (target_min, _) = target_component.get_size_range(direction.clone());
+        let (min_across, max_across) = component.get_size_range(opposite);
+        let fixed_across = match direction {
+            Direction::Horizontal => component.is_fixed_height(),
+            Direction::Vertical => component.is_fixed_width(),
+        };
+        let name = component.get_name();
+        let target_name = target_component.get_name();
+        if name == target_name
+            || fixed_across
+            || along(target_size) / 2 < std::cmp::max(min, target_min)
+            || across(target_size) < min_across
+            || across(target_size) > max_across
+        {
+            return false;
+        }
+        // Take the component out, keeping the sizes of the children around it
+        // so everything can be put back if the split cannot be sized
+        let Some((parent, _)) = self.search_parent_mut(path) else {
+            return false;
+        };
+        let Some(from_index) = parent
+            .get_children()
+            .iter()
+            .position(|c| c.as_base().get_name() == name)
+        else {
+            return false;
+        };
+        let from_sizes = parent
+            .get_children()
+            .iter()
+            .map(|c| (c.as_base().get_width(), c.as_base().get_height()))
+            .collect::<Vec<(u16, u16)>>();
+        let Some(ContainerChild::Component(component)) = parent.remove_child(&name) else {
+            return false;
+        };
+        let moved = match self.split_target(component, target, border, direction) {
+            Ok(()) => {
+                // Remove the container the component came from if left empty
+                if let Some((parent_path, _)) = path.rsplit_once('.') {
+                    if matches!(
+                        self.get_container().search_name(parent_path),
+                        Some((ContainerChild::Container(parent), _)) if parent.get_children().is_empty()
+                    ) {
+                        self.remove(parent_path);
+                    }
+                }
+                true
+            }
+            Err(component) => {
+                if let Some((parent, _)) = self.search_parent_mut(path) {
+                    let children = parent.get_children_mut();
+                    children.insert(from_index, ContainerChild::from(component));
+                    for (child, (width, height)) in children.iter_mut().zip(from_sizes) {
+                        let _ = child.as_base_mut().resize(width, height);
+                    }
+                    let _ = parent.refresh();
+                }
+                false
+            }
+        };
+        self.invalidate();
+        self.update_focus_history();
+        moved
+    }
+
+    /// Replaces the target with a new container holding it and the component
+    /// on the side of the border, giving back the component and leaving the
+    /// target as it was if they cannot both be sized to fit
+    fn split_target(
+        &mut self,
+        mut component: Component,
+        target: &str,
+        border: Border,
+        direction: Direction,
+    ) -> Result<(), Component> {
+        let Some((parent, target_name)) = self.search_parent_mut(target) else {
+            return Err(component);
+        };
+        let children = parent.get_children_mut();
+        let Some(index) = children
+            .iter()
+            .position(|c| c.as_base().get_name() == target_name)
+        else {
+            return Err(component);
+        };
+        // Each starts at the size of the target to share the space evenly
+        let target_child = children.remove(index);
+        let (width, height) = (
+            target_child.as_base().get_width(),
+            target_child.as_base().get_height(),
+        );
+        let name = component.get_name();
+        let _ = component.resize(width, height);
+        let split_name = get_split_name(children, &target_name);
+        let mut split = ContainerList::new(split_name, direction, true, 0, 0);
+        match border {
+            Border::Left | Border::Top => {
+                split
+                    .get_children_mut()
+                    .push(ContainerChild::from(component));
+                split.get_children_mut().push(target_child);
+            }
+            Border::Right | Border::Bottom => {
+                split.get_children_mut().push(target_child);
+                split
+                    .get_children_mut()
+                    .push(ContainerChild::from(component));
+            }
+        }
+        if split.resize(width, height).is_ok() {
+            parent
+                .get_children_mut()
+                .insert(index, ContainerChild::from(split));
+            return Ok(());
+        }
+        // Put the target back and hand the component back to be restored
+        let mut children = std::mem::take(split.get_children_mut());
+        let position = children
+            .iter()
+            .position(|c| c.as_base().get_name() == name)
+            .unwrap_or_default();
+        let ContainerChild::Component(component) = children.remove(position) else {
+            unreachable!("the moved child is always a component");
+        };
+        let mut target_child = children.remove(0);
+        let _ = target_child.as_base_mut().resize(width, height);
+        parent.get_children_mut().insert(index, target_child);
+        Err(component)
+    }
+
+    /// Returns the component being dragged and where it would be dropped
+    pub fn get_drop_target(&self) -> Option<(String, String, DropZone)> {
+        let drag = self.pane_drag.as_ref().filter(|drag| drag.active)?;
+        let (target, zone) = drag.target.clone()?;
+        Some((drag.path.clone(), target, zone))
+    }
+
+    /// Returns the path of the component with the text of its title at the
+    /// position
+    fn get_title_path(&self, x: u16, y: u16) -> Option<String> {
+        if self.zoomed.is_some() {
+            return None;
+        }
+        let (path, component, (x_local, y_local)) = self.get_component_at(x, y)?;
+        let width = std::cmp::min(
+            component.get_name().chars().count() as u16,
+            component.get_width().saturating_sub(2),
+        );
+        let on_title = component.get_border_width() > 0 && y_local == 0;
+        (on_title && x_local > 0 && x_local <= width).then_some(path)
+    }
+
+    /// Returns the component under the position and the zone of it the
+    /// position is in, with the outer quarter along each edge for the edges
+    fn get_drop_zone(&self, x: u16, y: u16) -> Option<(String, DropZone)> {
+        let (path, component, (x, y)) = self.get_component_at(x, y)?;
+        let (width, height) = (component.get_width() as f64, component.get_height() as f64);
+        let edges = [
+            (Border::Left, x as f64 / width),
+            (
+                Border::Right,
+                (component.get_width() - 1 - x) as f64 / width,
+            ),
+            (Border::Top, y as f64 / height),
+            (
+                Border::Bottom,
+                (component.get_height() - 1 - y) as f64 / height,
+            ),
+        ];
+        let zone = edges
+            .into_iter()
+            .filter(|(_, distance)| *distance < 0.25)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(DropZone::Center, |(border, _)| DropZone::Edge(border));
+        Some((path, zone))
+    }
+
+    /// Handles the mouse while dragging a component by its title, returning
+    /// true if the event was used by the drag
+    fn handle_pane_drag_mouse(&mut self, x: u16, y: u16, kind: Option<MouseEventKind>) -> bool {
+        match kind {
+            Some(MouseEventKind::Down(MouseButton::Left)) if self.drag_panes => {
+                self.pane_drag = self.get_title_path(x, y).map(|path| PaneDrag {
+                    x,
+                    y,
+                    path,
+                    active: false,
+                    target: None,
+                });
+                false
+            }
+            Some(MouseEventKind::Drag(MouseButton::Left)) => {
+                let Some(drag) = &mut self.pane_drag else {
+                    return false;
+                };
+                // Release the mouse in the container where it was pressed, so
+                // the title is not treated as a border to resize
+                if !drag.active {
+                    drag.active = true;
+                    let up = MouseEventKind::Up(MouseButton::Left);
+                    self.container.handle_mouse(drag.x, drag.y, Some(up));
+                }
+                let path = drag.path.clone();
+                let target = self
+                    .get_drop_zone(x, y)
+                    .filter(|(target, _)| *target != path);
+                if let Some(drag) = self.pane_drag.as_mut().filter(|d| d.target != target) {
+                    drag.target = target;
+                    self.invalidate();
+                }
+                true
+            }
+            Some(MouseEventKind::Up(MouseButton::Left)) => match self.pane_drag.take() {
+                Some(PaneDrag {
+                    path,
+                    active: true,
+                    target,
+                    ..
+                }) => {
+                    if let Some((target, zone)) = target {
+                        self.move_pane(&path, &target, zone);
+                    }
+                    self.invalidate();
+                    true
+                }
+                _ => false,
+            },
+            Some(MouseEventKind::Moved) => false,
+            _ => {
+                let active = self.pane_drag.take().is_some_and(|drag| drag.active);
+                if active {
+                    self.invalidate();
+                }
+                active
+            }
+        }
+    }
+
+    /// Draws where the dragged component would be dropped
+    fn render_drop_target(&self, area: Rect, buf: &mut Buffer) {
+        let Some((_, target, zone)) = self.get_drop_target() else {
+            return;
+        };
+        let Some((_, component, pos, part)) = self
+            .get_container()
+            .search_component_views()
+            .into_iter()
+            .find(|(path, _, _, _)| *path == target)
+        else {
+            return;
+        };
+        let (width, height) = (component.get_width(), component.get_height());
+        let rect = Rect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        };
+        let rect = match zone {
+            DropZone::Center => rect,
+            DropZone::Edge(Border::Left) => Rect {
+                width: width / 2,
+                ..rect
+            },
+            DropZone::Edge(Border::Right) => Rect {
+                x: rect.x + width / 2,
+                width: width - width / 2,
+                ..rect
+            },
+            DropZone::Edge(Border::Top) => Rect {
+                height: height / 2,
+                ..rect
+            },
+            DropZone::Edge(Border::Bottom) => Rect {
+                y: rect.y + height / 2,
+                height: height - height / 2,
+                ..rect
+            },
+        };
+        // Only the part of the zone in view is drawn, in the coordinates of
+        // the root
+        let Some(rect) = clip_rect(rect, part) else {
+            return;
+        };
+        let rect = Rect {
+            x: area.x + pos.x + rect.x - part.x,
+            y: area.y + pos.y + rect.y - part.y,
+            ..rect
+        };
+        if let Some(rect) = clip_rect(rect, area) {
+            buf.set_style(rect, Style::default().bg(Color::Blue));
+        }
+    }
+
+    /// Replaces the component at the position with a placeholder of the size,
+    /// returning the component
+    fn replace_component(&mut self, pos: ComponentPos, size: (u16, u16)) -> Option<Component> {
+        let (component, _) = self.get_container_mut().search_position_mut(pos)?;
+        let mut placeholder = Component::new(
+            component.get_name(),
+            0,
+            Box::new(ComponentWidgetSimple::new()),
+        );
+        placeholder.resize(size.0, size.1).ok()?;
+        Some(std::mem::replace(component, placeholder))
+    }
+
+    /// Puts the component back in place of the placeholder at the position
+    fn put_component(&mut self, pos: ComponentPos, component: Component) {
+        if let Some((placeholder, _)) = self.get_container_mut().search_position_mut(pos) {
+            *placeholder = component;
+        }
+    }
+
+    /// Resizes each component to the size of the other, returning them back
+    /// at their own sizes if either cannot take the size of the other
+    fn swap_sizes(
+        mut a: Component,
+        mut b: Component,
+        a_size: (u16, u16),
+        b_size: (u16, u16),
+    ) -> (Component, Component, bool) {
+        let fits = |c: &mut Component, size: (u16, u16)| {
+            c.resize(size.0, size.1).is_ok() && (c.get_width(), c.get_height()) == size
+        };
+        if fits(&mut a, b_size) && fits(&mut b, a_size) {
+            return (a, b, true);
+        }
+        let _ = a.resize(a_size.0, a_size.1);
+        let _ = b.resize(b_size.0, b_size.1);
+        (a, b, false)
+    }
+
+    /// Starts moving the borders of the focused component with the arrow keys
+    /// until escape or enter is pressed
+    pub fn start_resize_mode(&mut self) -> bool {
+        self.unzoom();
+        if self.get_container().search_focused_name().is_none() {
+            return false;
+        }
+        self.resize_mode = true;
+        self.invalidate_status();
+        true
+    }
+
+    pub fn stop_resize_mode(&mut self) {
+        if self.resize_mode {
+            self.resize_mode = false;
+            self.invalidate();
+        }
+    }
+
+    pub fn is_resize_mode(&self) -> bool {
+        self.resize_mode
+    }
+
+    /// Moves the nearest border of the focused component in the direction of
+    /// the arrow key, growing the component if the border is on that side and
+    /// shrinking it otherwise
+    fn handle_resize_mode_key(&mut self, e: KeyEvent) -> EventResult {
+        let border = match e.code {
+            KeyCode::Up => Border::Top,
+            KeyCode::Down => Border::Bottom,
+            KeyCode::Left => Border::Left,
+            KeyCode::Right => Border::Right,
+            KeyCode::Esc | KeyCode::Enter => {
+                self.stop_resize_mode();
+                return EventResult::Redraw;
+            }
+            _ => return EventResult::Consumed,
+        };
+        let Some(path) = self.get_container().search_focused_name() else {
+            self.stop_resize_mode();
+            return EventResult::Redraw;
+        };
+        let step = self.resize_step as i16;
+        if !self.resize_border(&path, border.clone(), step) {
+            self.resize_border(&path, border.opposite(), -step);
+        }
+        EventResult::Redraw
+    }
+
+    /// Returns the path of the zoomed component
+    pub fn get_zoomed(&self) -> Option<String> {
+        self.zoomed.as_ref().map(|(path, _, _)| path.clone())
+    }
+
+    /// Runs a layout command, returning false if it could not be run
+    pub fn run_command(&mut self, command: RootCommand) -> bool {
+        let result = match command {
+            RootCommand::FocusPrevious => self.focus_previous(),
+            RootCommand::QuickJump => {
+                self.unzoom();
+                self.start_quick_jump();
+                true
+            }
+            RootCommand::FocusMove(border) => self.focus_move(border),
+            RootCommand::Split(direction) => self.split(direction),
+            RootCommand::Close => self.close().is_some(),
+            RootCommand::Zoom => self.toggle_zoom(),
+            RootCommand::Resize(border, delta) => self.resize_focused(border, delta),
+            RootCommand::ResizeMode => self.start_resize_mode(),
+            RootCommand::Equalize(recursive) => self.equalize(recursive),
+            RootCommand::Rotate(reverse) => self.rotate(reverse),
+            RootCommand::Swap(border) => self.swap_focused(border),
+            RootCommand::Flip => self.flip(),
+            RootCommand::NextLayout => self.next_layout(),
+            RootCommand::Workspace(index) => self.switch_workspace(index),
+            RootCommand::NextWorkspace(reverse) => self.next_workspace(reverse),
+        };
+        self.update_focus_history();
+        result
+    }
+
+    /// Moves focus to the next component across the border of the focused
+    /// component, keeping the focus it had
+    pub fn focus_move(&mut self, border: Border) -> bool {
+        self.unzoom();
+        let Some(path) = self.get_container().search_focused_name() else {
+            return false;
+        };
+        let focus = self.get_container().get_focus();
+        // Moving between components requires partial focus
+        if let FocusResult::Focus((component, _)) = self.get_container_mut().search_focused_mut() {
+            component.set_focus(Focus::PartialFocus);
+        }
+        let code = match border {
+            Border::Top => KeyCode::Up,
+            Border::Bottom => KeyCode::Down,
+            Border::Left => KeyCode::Left,
+            Border::Right => KeyCode::Right,
+        };
+        self.container
+            .handle_key(KeyEvent::new(code, KeyModifiers::empty()));
+        if let FocusResult::PartialFocus((component, _)) =
+            self.get_container_mut().search_focused_mut()
+        {
+            component.set_focus(focus);
+        }
+        self.get_container().search_focused_name() != Some(path)
+    }
+
+    /// Splits the focused component in two along the direction, using the
+    /// split factory to create the new component and moving focus to it
+    pub fn split(&mut self, direction: Direction) -> bool {
+        self.unzoom();
+        let Some(path) = self.get_container().search_focused_name() else {
+            return false;
+        };
+        let Some(factory) = &mut self.split_factory else {
+            return false;
+        };
+        let mut new_component = factory(&path);
+        if let Some(focus_policy) = &self.focus_policy {
+            new_component.set_focus_policy(focus_policy.clone());
+        }
+        let new_child = ContainerChild::from(new_component);
+        let new_name = new_child.as_base().get_name();
+        let focus = self.get_container().get_focus();
+        let Some((parent, name)) = self.search_parent_mut(&path) else {
+            return false;
+        };
+        let children = parent.get_children_mut();
+        let Some(index) = children.iter().position(|c| c.as_base().get_name() == name) else {
+            return false;
+        };
+        // Replace the component with a new container holding both
+        let old_child = children.remove(index);
+        let (width, height) = (
+            old_child.as_base().get_width(),
+            old_child.as_base().get_height(),
+        );
+        let split_name = get_split_name(children, &name);
+        let mut split = ContainerList::new(split_name.clone(), direction, true, 0, 0);
+        split.get_children_mut().push(old_child);
+        split.get_children_mut().push(new_child);
+        if split.resize(width, height).is_err() {
+            let mut old_child = split.get_children_mut().remove(0);
+            let _ = old_child.as_base_mut().resize(width, height);
+            parent.get_children_mut().insert(index, old_child);
+            return false;
+        }
+        parent
+            .get_children_mut()
+            .insert(index, ContainerChild::from(split));
+        // Keep the history of the split component under its new path
+        let prefix = match path.rsplit_once('.') {
+            Some((parent, _)) => format!("{parent}.{split_name}"),
+            None => split_name,
+        };
+        for (p, _) in &mut self.focus_history {
+            if *p == path {
+                *p = format!("{prefix}.{name}");
+            }
+        }
+        // Focus the newly created component
+        self.focus_name(&format!("{prefix}.{new_name}"), focus);
+        true
+    }
+
+    /// Removes the focused component, moving focus to the most recently
+    /// focused component
+    pub fn close(&mut self) -> Option<ContainerChild> {
+        self.unzoom();
+        let path = self.get_container().search_focused_name()?;
+        self.remove(&path)
+    }
+
+    /// Toggles the focused component taking up the whole root, all input is
+    /// sent directly to the zoomed component
+    pub fn toggle_zoom(&mut self) -> bool {
+        if self.zoomed.is_some() {
+            self.unzoom();
+            return true;
+        }
+        let Some(path) = self.get_container().search_focused_name() else {
+            return false;
+        };
+        let (width, height) = (self.container.get_width(), self.container.get_height());
+        let Some((ContainerChild::Component(component), _)) =
+            self.get_container_mut().search_name_mut(&path)
+        else {
+            return false;
+        };
+        let (old_width, old_height) = (component.get_width(), component.get_height());
+        if component.resize(width, height).is_err() {
+            return false;
+        }
+        self.zoomed = Some((path, old_width, old_height));
+        self.invalidate();
+        true
+    }
+
+    fn unzoom(&mut self) {
+        let Some((path, width, height)) = self.zoomed.take() else {
+            return;
+        };
+        if let Some((ContainerChild::Component(component), _)) =
+            self.get_container_mut().search_name_mut(&path)
+        {
+            let _ = component.resize(width, height);
+        }
+        // Lay out the containers holding the component again, outermost first,
+        // so their children fit around the component at its restored size
+        let _ = self.container.refresh();
+        let parents = path.match_indices('.').map(|(i, _)| path[..i].to_string());
+        for parent in parents.collect::<Vec<String>>() {
+            if let Some((ContainerChild::Container(container), _)) =
+                self.get_container_mut().search_name_mut(&parent)
+            {
+                let _ = container.refresh();
+            }
+        }
+        self.invalidate();
+    }
+
+    fn get_zoomed_mut(&mut self) -> Option<&mut Component> {
+        let (path, _, _) = self.zoomed.clone()?;
+        match self.get_container_mut().search_name_mut(&path) {
+            Some((ContainerChild::Component(component), _)) => Some(component),
+            _ => None,
+        }
+    }
+
+    /// Returns the container holding the child at the path, and the name of
+    /// the child in that container
+    fn search_parent_mut(&mut self, path: &str) -> Option<(&mut dyn Container, String)> {
+        match path.rsplit_once('.') {
+            Some((parent, name)) => match self.get_container_mut().search_name_mut(parent) {
+                Some((ContainerChild::Container(parent), _)) => {
+                    Some((parent.as_container_mut(), name.to_string()))
+                }
+                _ => None,
+            },
+            None => Some((self.get_container_mut(), path.to_string())),
+        }
+    }
+
+    /// Checks the key against the prefix key and its bindings, returning true
+    /// if the key was used
+    fn handle_prefix_key(&mut self, e: &KeyEvent) -> bool {
+        let Some(prefix_key) = self.prefix_key.clone() else {
+            return false;
+        };
+        let Some(pending) = self.prefix_pending.take() else {
+            if prefix_key.matches(e) {
+                self.prefix_pending = Some(Instant::now());
+                self.invalidate();
+                return true;
+            }
+            return false;
+        };
+        self.invalidate();
+        // Treat the key normally if the prefix timed out
+        if pending.elapsed() > self.prefix_timeout {
+            return false;
+        }
+        // Pressing the prefix key twice sends it on to the focused component
+        if prefix_key.matches(e) {
+            return false;
+        }
+        let command = self
+            .prefix_bindings
+            .iter()
+            .find(|(key, _)| key.matches(e))
+            .map(|(_, command)| command.clone());
+        if let Some(command) = command {
+            self.run_command(command);
+        }
+        true
+    }
+
+    /// Runs the commands and handles the keys from the keymap, returning the
+    /// result of the last one
+    fn handle_keymap_matches(&mut self, matches: Vec<KeyMatch<RootCommand>>) -> EventResult {
+        let mut result = EventResult::Consumed;
+        for m in matches {
+            result = match m {
+                KeyMatch::Binding(command) => {
+                    self.run_command(command);
+                    EventResult::Redraw
+                }
+                KeyMatch::Key(e) => self.handle_unbound_key(e),
+            }
+        }
+        result
+    }
+
+    fn handle_navigation_matches(&mut self, matches: Vec<KeyMatch<RootCommand>>) -> EventResult {
+        let mut result = EventResult::Consumed;
+        for m in matches {
+            result = match m {
+                KeyMatch::Binding(command) => {
+                    self.run_command(command);
+                    EventResult::Redraw
+                }
+                KeyMatch::Key(e) => self.handle_container_key(e),
+            }
+        }
+        result
+    }
+
+    /// Handles a key not bound in the keymap
+    fn handle_unbound_key(&mut self, e: KeyEvent) -> EventResult {
+        if matches!(&self.quick_jump_key, Some(key) if key.matches(&e)) {
+            self.run_command(RootCommand::QuickJump);
+            return EventResult::Redraw;
+        }
+        if matches!(&self.focus_previous_key, Some(key) if key.matches(&e)) {
+            self.run_command(RootCommand::FocusPrevious);
+            return EventResult::Redraw;
+        }
+        if let Some(result) = self.handle_hotkey(e, HotkeyPriority::Before) {
+            return result;
+        }
+        // Check navigation bindings only while no component has full focus
+        if self.get_container().get_focus() != Focus::Focus {
+            let matches = self.navigation_keymap.handle_key(e);
+            self.invalidate_status();
+            return self.handle_navigation_matches(matches);
+        }
+        self.handle_container_key(e)
+    }
+
+    /// Sends a key on to the focused component, checking the hotkeys if the
+    /// key was ignored
+    fn handle_container_key(&mut self, e: KeyEvent) -> EventResult {
+        match self.handle_focused_key(e) {
+            EventResult::Ignored => self
+                .handle_hotkey(e, HotkeyPriority::After)
+                .unwrap_or(EventResult::Ignored),
+            result => result,
+        }
+    }
+
+    fn handle_focused_key(&mut self, e: KeyEvent) -> EventResult {
+        // Send keys directly to the zoomed component, leaving the zoom if
+        // focus moves out of it
+        if let Some(component) = self.get_zoomed_mut() {
+            return match component.handle_key(e) {
+                EventResult::Border(border) => {
+                    self.run_command(RootCommand::FocusMove(border));
+                    EventResult::Redraw
+                }
+                result => result,
+            };
+        }
+        let result = self.container.handle_key(e);
+        self.update_focus_history();
+        result
+    }
+
+    /// Redraws everything under the status while it is shown
+    fn invalidate_status(&mut self) {
+        if self.show_status && (self.keymap.is_pending() || self.navigation_keymap.is_pending()) {
+            self.invalidate();
+        }
+    }
+
+    /// Shows a label over every component that can take focus, typing a label
+    /// focuses that component and any other key cancels
+    pub fn start_quick_jump(&mut self) {
+        let components = self
+            .get_container()
+            .search_component_views()
+            .into_iter()
+            .filter(|(_, component, _, _)| component.is_focusable())
+            .map(|(path, _, pos, part)| {
+                let rect = Rect {
+                    x: pos.x,
+                    y: pos.y,
+                    ..part
+                };
+                (path, rect)
+            })
+            .collect::<Vec<(String, Rect)>>();
+        let labels = quick_jump_labels(components.len())
+            .into_iter()
+            .zip(components)
+            .map(|(label, (path, rect))| QuickJumpLabel { label, path, rect })
+            .collect();
+        self.quick_jump = Some(QuickJump {
+            labels,
+            typed: String::new(),
+        });
+        self.invalidate();
+    }
+
+    pub fn stop_quick_jump(&mut self) {
+        if self.quick_jump.take().is_some() {
+            self.invalidate();
+        }
+    }
+
+    /// Returns the labels and paths of the components while quick jumping
+    pub fn get_quick_jump_labels(&self) -> Vec<(String, String)> {
+        let Some(quick_jump) = &self.quick_jump else {
+            return Vec::new();
+        };
+        quick_jump
+            .labels
+            .iter()
+            .map(|l| (l.label.clone(), l.path.clone()))
+            .collect()
+    }
+
+    fn handle_quick_jump_key(&mut self, e: KeyEvent) {
+        let Some(quick_jump) = &mut self.quick_jump else {
+            return;
+        };
+        let KeyCode::Char(c) = e.code else {
+            self.stop_quick_jump();
+            return;
+        };
+        quick_jump.typed.push(c);
+        let typed = quick_jump.typed.clone();
+        let matched = quick_jump
+            .labels
+            .iter()
+            .find(|l| l.label == typed)
+            .map(|l| l.path.clone());
+        let partial = quick_jump
+            .labels
+            .iter()
+            .any(|l| l.label.starts_with(&typed));
+        // Keep waiting if only part of a longer label has been typed
+        if matched.is_none() && partial {
+            return;
+        }
+        self.stop_quick_jump();
+        if let Some(path) = matched {
+            self.focus_name(&path, Focus::Focus);
+        }
+    }
+
+    /// Returns the paths of the focused components, most recent first
+    pub fn get_focus_history(&self) -> Vec<String> {
+        self.focus_history
+            .iter()
+            .filter(|(path, _)| is_focusable(self.get_container(), path))
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    /// Focuses the component at the index in the focus history, where zero is
+    /// the most recent, restoring the focus it had and returning false if the
+    /// index does not exist
+    pub fn focus_history(&mut self, index: usize) -> bool {
+        let container = self.container.as_container();
+        self.focus_history
+            .retain(|(path, _)| is_focusable(container, path));
+        let Some((path, focus)) = self.focus_history.get(index).cloned() else {
+            return false;
+        };
+        let focused = self.focus_name(&path, focus);
+        self.update_focus_history();
+        focused
+    }
+
+    /// Focuses the previously focused component, returning false if there is
+    /// none
+    pub fn focus_previous(&mut self) -> bool {
+        self.focus_history(1)
+    }
+
+    /// Moves focus to the component with the matching path, returning false
+    /// if there is no matching component that can take focus
+    pub fn focus_name(&mut self, path: &str, focus: Focus) -> bool {
+        if !is_focusable(self.get_container(), path) {
+            return false;
+        }
+        self.clear_focus();
+        if let Some((ContainerChild::Component(component), _)) =
+            self.get_container_mut().search_name_mut(path)
+        {
+            component.set_focus(focus);
+        }
+        true
+    }
+
+    /// Removes the child with the matching path from its container, moving
+    /// focus to the most recently focused component if the child had focus
+    pub fn remove(&mut self, path: &str) -> Option<ContainerChild> {
+        if self.get_zoomed().as_deref() == Some(path) {
+            self.unzoom();
+        }
+        let (parent, name) = self.search_parent_mut(path)?;
+        let child = parent.remove_child(&name);
+        // Remove any containers left empty
+        if let Some((parent_path, _)) = path.rsplit_once('.') {
+            if matches!(
+                self.get_container().search_name(parent_path),
+                Some((ContainerChild::Container(parent), _)) if parent.get_children().is_empty()
+            ) {
+                self.remove(parent_path);
+            }
+        }
+        self.update_focus_history();
+        child
+    }
+
+    fn clear_focus(&mut self) {
+        while let FocusResult::Focus((component, _)) | FocusResult::PartialFocus((component, _)) =
+            self.get_container_mut().search_focused_mut()
+        {
+            component.set_focus(Focus::None);
+        }
+    }
+
+    /// Records the currently focused component in the focus history, and
+    /// falls back to the most recent surviving component if the focused
+    /// component was removed, disabled or made unable to take focus
+    ///
+    /// The history is only walked when focus has changed since the last
+    /// update, entries that can no longer be focused are skipped when read
+    fn update_focus_history(&mut self) {
+        let container = self.container.as_container();
+        let focused = container.search_focused_name();
+        let focus = match &focused {
+            Some(path) => container
+                .search_name(path)
+                .map(|(child, _)| child.as_base().get_focus()),
+            None => None,
+        };
+        match (&focused, self.focus_history.first()) {
+            (Some(path), Some((p, f))) if p == path && Some(f) == focus.as_ref() => return,
+            (None, Some((p, _))) if is_focusable(container, p) => return,
+            (None, None) => return,
+            _ => {}
+        }
+        // Forget any components that can no longer be focused
+        let lost =
+            matches!(self.focus_history.first(), Some((path, _)) if !is_focusable(container, path));
+        self.focus_history
+            .retain(|(path, _)| is_focusable(container, path));
+        match focused {
+            Some(path) => {
+                self.focus_history.retain(|(p, _)| *p != path);
+                self.focus_history
+                    .insert(0, (path, focus.unwrap_or(Focus::None)));
+            }
+            None if lost => {
+                if let Some((path, _)) = self.focus_history.first().cloned() {
+                    self.focus_name(&path, Focus::PartialFocus);
+                    self.focus_history[0].1 = Focus::PartialFocus;
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+impl ComponentBase for Root {
+    fn handle_input(&mut self, event: CrosstermEvent) -> EventResult {
+        self.menu_result = None;
+        let result = handle_input_default(self, event);
+        // Return the result of choosing a context menu item with the mouse
+        self.take_menu_result().unwrap_or(result)
+    }
+
+    fn handle_mouse(&mut self, x: u16, y: u16, kind: Option<MouseEventKind>) {
+        // Only the result of this event can be taken afterwards
+        self.menu_result = None;
+        if !matches!(kind, Some(MouseEventKind::Moved)) {
+            self.stop_quick_jump();
+        }
+        if self.handle_menu_mouse(x, y, kind) {
+            return;
+        }
+        if self.handle_workspace_bar_mouse(x, y, kind) {
+            self.container.handle_mouse_leave();
+            return;
+        }
+        if let Some(MouseEventKind::Down(MouseButton::Right)) = kind {
+            if self.open_menu(x, y) {
+                return;
+            }
+        }
+        if self.handle_pane_drag_mouse(x, y, kind) {
+            self.update_focus_history();
+            return;
+        }
+        match self.get_zoomed_mut() {
+            Some(component) => component.handle_mouse(x, y, kind),
+            None => self.container.handle_mouse(x, y, kind),
+        }
+        // Follow a mouse down with the click and how many were made in a row
+        if let Some(MouseEventKind::Down(button)) = kind {
+            let count = self.count_click(x, y, button);
+            match self.get_zoomed_mut() {
+                Some(component) => component.handle_click(x, y, button, count),
+                None => self.container.handle_click(x, y, button, count),
+            }
+            if count == 2 && button == MouseButton::Left && self.is_title(x, y) {
+                if let Some(command) = self.title_double_click.clone() {
+                    self.run_command(command);
+                }
+            }
+        }
+        self.update_focus_history();
+    }
+
+    fn handle_mouse_leave(&mut self) {
+        match self.get_zoomed_mut() {
+            Some(component) => component.handle_mouse_leave(),
+            None => self.container.handle_mouse_leave(),
+        }
+    }
+
+    fn handle_mouse_captured(&mut self, x: i32, y: i32, kind: MouseEventKind) {
+        match self.get_zoomed_mut() {
+            Some(component) => component.handle_mouse_captured(x, y, kind),
+            None => self.container.handle_mouse_captured(x, y, kind),
+        }
+    }
+
+    fn handle_click(&mut self, x: u16, y: u16, button: MouseButton, count: u8) {
+        match self.get_zoomed_mut() {
+            Some(component) => component.handle_click(x, y, button, count),
+            None => self.container.handle_click(x, y, button, count),
+        }
+    }
+
+    fn handle_scroll(&mut self, x: u16, y: u16, kind: MouseEventKind, modifiers: KeyModifiers) {
+        self.stop_quick_jump();
+        if let Some(component) = self.get_zoomed_mut() {
+            component.handle_scroll(x, y, kind, modifiers);
+            return;
+        }
+        self.container.handle_scroll(x, y, kind, modifiers);
+        self.update_focus_history();
+    }
+
+    fn handle_key(&mut self, e: KeyEvent) -> EventResult {
+        // Terminals that report releases would otherwise run everything twice
+        if e.kind == KeyEventKind::Release {
+            return EventResult::Ignored;
+        }
+        if self.menu.is_some() {
+            return self.handle_menu_key(e);
+        }
+        if self.resize_mode {
+            return self.handle_resize_mode_key(e);
+        }
+        if self.quick_jump.is_some() {
+            self.handle_quick_jump_key(e);
+            self.update_focus_history();
+            return EventResult::Redraw;
+        }
+        if self.handle_prefix_key(&e) {
+            return EventResult::Redraw;
+        }
+        let matches = self.keymap.handle_key(e);
+        self.invalidate_status();
+        self.handle_keymap_matches(matches)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> EventResult {
+        if self.quick_jump.is_some() {
+            return EventResult::Ignored;
+        }
+        match self.get_zoomed_mut() {
+            Some(component) => component.handle_paste(text),
+            None => self.container.handle_paste(text),
+        }
+    }
+
+    fn handle_terminal_focus(&mut self, focused: bool) {
+        self.container.handle_terminal_focus(focused);
+        // Inactive workspaces must know the terminal focus once switched to
+        for workspace in &mut self.workspaces {
+            if let Some(container) = &mut workspace.container {
+                container.handle_terminal_focus(focused);
+            }
+        }
+    }
+
+    fn handle_update(&mut self) {
+        self.container.handle_update();
+        if self.update_inactive {
+            for workspace in &mut self.workspaces {
+                if let Some(container) = &mut workspace.container {
+                    container.handle_update();
+                }
+            }
+        }
+        // Resolve any key sequences left waiting too long
+        let matches = self.keymap.handle_timeout();
+        self.handle_keymap_matches(matches);
+        let matches = self.navigation_keymap.handle_timeout();
+        self.handle_navigation_matches(matches);
+        // Cancel the prefix if no command followed it in time
+        if matches!(self.prefix_pending, Some(pending) if pending.elapsed() > self.prefix_timeout) {
+            self.prefix_pending = None;
+            self.invalidate();
+        }
+        self.update_focus_history();
+    }
+
+    fn invalidate(&mut self) {
+        self.container.invalidate();
+    }
+
+    fn resize(&mut self, width: u16, height: u16) -> Result<(), ResizeError> {
+        let zoomed = self.get_zoomed();
+        self.unzoom();
+        let bar_height = self.get_workspace_bar_height();
+        self.container
+            .resize(width, height.saturating_sub(bar_height))?;
+        if let Some(path) = zoomed {
+            if self.focus_name(&path, self.get_container().get_focus()) {
+                self.toggle_zoom();
+            }
+        }
+        Ok(())
+    }
+
+    fn get_width(&self) -> u16 {
+        self.container.get_width()
+    }
+
+    fn get_height(&self) -> u16 {
+        self.container.get_height() + self.get_workspace_bar_height()
+    }
+
+    fn is_fixed_width(&self) -> bool {
+        self.container.is_fixed_width()
+    }
+
+    fn is_fixed_height(&self) -> bool {
+        self.container.is_fixed_height()
+    }
+
+    fn get_size_range(&self, direction: Direction) -> (u16, u16) {
+        let (min, max) = self.container.get_size_range(direction.clone());
+        match direction {
+            Direction::Horizontal => (min, max),
+            Direction::Vertical => {
+                let bar_height = self.get_workspace_bar_height();
+                (
+                    min.saturating_add(bar_height),
+                    max.saturating_add(bar_height),
+                )
+            }
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.container.is_focusable()
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let container_area = Rect {
+            height: area.height.saturating_sub(self.get_workspace_bar_height()),
+            ..area
+        };
+        match self.get_zoomed_mut() {
+            Some(component) => component.render(container_area, buf),
+            None => self.container.render(container_area, buf),
+        }
+        self.render_workspace_bar(area, buf);
+        // Draw any pending input state in the bottom right corner
+        if let Some(status) = self.get_status().filter(|_| self.show_status) {
+            let text = format!(" {status} ");
+            let width = std::cmp::min(text.len() as u16, area.width);
+            if width > 0 && area.height > 0 {
+                let style = Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD);
+                let x = area.x + area.width - width;
+                let y = area.y + area.height - 1;
+                buf.set_stringn(x, y, text, width as usize, style);
+            }
+        }
+        self.render_drop_target(area, buf);
+        self.render_menu(area, buf);
+        // Draw quick jump labels in the middle of their components
+        let Some(quick_jump) = &self.quick_jump else {
+            return;
+        };
+        let style = Style::default()
+            .fg(Color::Black)
+            .bg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        for label in &quick_jump.labels {
+            // Pad the label to make it stand out, shrinking to fit if needed
+            let width = std::cmp::min(label.label.len() as u16 + 4, label.rect.width);
+            let height = std::cmp::min(3, label.rect.height);
+            let rect = Rect {
+                x: area.x + label.rect.x + (label.rect.width - width) / 2,
+                y: area.y + label.rect.y + (label.rect.height - height) / 2,
+                width,
+                height,
+            };
+            let Some(rect) = clip_rect(rect, area) else {
+                continue;
+            };
+            buf.set_style(rect, style);
+            for y in rect.y..(rect.y + rect.height) {
+                for x in rect.x..(rect.x + rect.width) {
+                    buf.get_mut(x, y).set_symbol(" ");
+                }
+            }
+            let text = format!("{:^width$}", label.label, width = rect.width as usize);
+            buf.set_stringn(
+                rect.x,
+                rect.y + rect.height / 2,
+                text,
+                rect.width as usize,
+                style,
+            );
+        }
+    }
+
+    fn get_focus(&self) -> Focus {
+        self.container.get_focus()
+    }
+
+    fn get_name(&self) -> String {
+        self.container.get_name()
+    }
+
+    fn get_border(&self, x: u16, y: u16) -> Option<Border> {
+        self.container.get_border(x, y)
+    }
+
+    fn get_border_along(&self, x: u16, y: u16, direction: Direction) -> Option<Border> {
+        self.container.get_border_along(x, y, direction)
+    }
+}
@@ -5,9 +5,12 @@ use std::time::{self, Duration, Instant};
 
 use crossbeam::channel::{unbounded, Sender};
 
-use crossterm::event::{KeyCode, KeyEvent, MouseEventKind};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event as CrosstermEvent},
+    event::{
+        self, DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture, Event as CrosstermEvent,
+    },
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     tty::IsTty,
     QueueableCommand, Result as CrosstermResult,
@@ -22,13 +25,21 @@ use tui::{
 use tui_tiling::{
     component::{Component, ComponentBase, ComponentBaseWidget, ComponentWidget},
     container::{list::ContainerList, Container, ContainerChild},
-    ResizeError,
+    keymap::Keymap,
+    root::{vim_window_keymap, HotkeyAction, HotkeyPriority, Root, RootCommand},
+    EventResult, KeyBinding, MenuItem, ResizeError,
 };
 
 pub struct TestComponentWidget {
     print_last: bool,
     mouse_last: Option<(u16, u16, MouseEventKind)>,
+    mouse_captured_last: Option<(i32, i32, MouseEventKind)>,
+    click_last: Option<(u16, u16, MouseButton, u8)>,
+    menu_last: Option<String>,
     key_last: Option<KeyEvent>,
+    paste_last: Option<String>,
+    terminal_focused: bool,
+    hovered: bool,
 }
 
 impl TestComponentWidget {
@@ -36,9 +47,47 @@ impl TestComponentWidget {
         Self {
             print_last,
             mouse_last: None,
+            mouse_captured_last: None,
+            click_last: None,
+            menu_last: None,
             key_last: None,
+            paste_last: None,
+            terminal_focused: true,
+            hovered: false,
         }
     }
+
+    pub fn get_mouse_last(&self) -> Option<(u16, u16, MouseEventKind)> {
+        self.mouse_last
+    }
+
+    pub fn get_mouse_captured_last(&self) -> Option<(i32, i32, MouseEventKind)> {
+        self.mouse_captured_last
+    }
+
+    pub fn get_click_last(&self) -> Option<(u16, u16, MouseButton, u8)> {
+        self.click_last
+    }
+
+    pub fn get_menu_last(&self) -> Option<String> {
+        self.menu_last.clone()
+    }
+
+    pub fn get_key_last(&self) -> Option<KeyEvent> {
+        self.key_last
+    }
+
+    pub fn get_paste_last(&self) -> Option<String> {
+        self.paste_last.clone()
+    }
+
+    pub fn is_terminal_focused(&self) -> bool {
+        self.terminal_focused
+    }
+
+    pub fn is_hovered(&self) -> bool {
+        self.hovered
+    }
 }
 
 impl ComponentWidget for TestComponentWidget {
@@ -47,17 +96,55 @@ impl ComponentWidget for TestComponentWidget {
         true
     }
 
-    fn handle_key(&mut self, e: KeyEvent) -> bool {
+    fn handle_mouse_captured(&mut self, x: i32, y: i32, e: MouseEventKind) -> bool {
+        self.mouse_captured_last = Some((x, y, e));
+        true
+    }
+
+    fn handle_click(&mut self, x: u16, y: u16, button: MouseButton, count: u8) -> bool {
+        self.click_last = Some((x, y, button, count));
+        true
+    }
+
+    fn handle_key(&mut self, e: KeyEvent) -> EventResult {
         self.key_last = Some(e);
+        EventResult::Redraw
+    }
+
+    fn handle_hover(&mut self, hovered: bool) -> bool {
+        self.hovered = hovered;
+        true
+    }
+
+    fn handle_paste(&mut self, text: &str) -> EventResult {
+        self.paste_last = Some(text.to_string());
+        EventResult::Redraw
+    }
+
+    fn handle_terminal_focus(&mut self, focused: bool) -> bool {
+        self.terminal_focused = focused;
         true
     }
 
+    fn get_menu_items(&self, position: Option<(u16, u16)>) -> Vec<MenuItem> {
+        match position {
+            Some(_) => vec![MenuItem::new("Copy", "copy")],
+            None => Vec::new(),
+        }
+    }
+
+    fn handle_menu(&mut self, action: &str) -> EventResult {
+        self.menu_last = Some(action.to_string());
+        EventResult::Redraw
+    }
+
     fn handle_update(&mut self) -> bool {
         false
     }
 
     fn resize(&mut self, _: u16, _: u16) {}
 
+    #[allow(clippy::useless_format, clippy::useless_conversion)]
     fn render(&mut self, area: Rect, buf: &mut Buffer) {
         for x in 0..area.width {
             buf.get_mut(area.x + x, area.y).symbol = format!("#");
@@ -94,6 +181,7 @@ impl ComponentWidget for TestComponentWidget {
     }
 }
 
+#[allow(clippy::clone_on_copy)]
 pub fn render_helper(component_base: &mut dyn ComponentBase) -> Buffer {
     let rect = Rect::new(
         0,
@@ -122,6 +210,7 @@ pub fn get_tui(print_last: bool) -> Result<Box<dyn Container>, ResizeError> {
         0,
         0,
     )
+    .menu_items(vec![MenuItem::new("Quit", "quit")])
     .from_children(vec![
         ContainerChild::from(
             ContainerList::new(String::from("vertical"), Direction::Vertical, true, 0, 0)
@@ -159,6 +248,8 @@ fn setup_terminal() -> CrosstermResult<Terminal<CrosstermBackend<Stdout>>> {
     enable_raw_mode().unwrap();
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
     terminal.backend_mut().queue(EnableMouseCapture)?;
+    terminal.backend_mut().queue(EnableBracketedPaste)?;
+    terminal.backend_mut().queue(EnableFocusChange)?;
     terminal.backend_mut().queue(EnterAlternateScreen)?;
     terminal.backend_mut().flush()?;
     terminal.clear()?;
@@ -167,6 +258,8 @@ fn setup_terminal() -> CrosstermResult<Terminal<CrosstermBackend<Stdout>>> {
 
 fn cleanup_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> CrosstermResult<()> {
     terminal.backend_mut().queue(DisableMouseCapture)?;
+    terminal.backend_mut().queue(DisableBracketedPaste)?;
+    terminal.backend_mut().queue(DisableFocusChange)?;
     terminal.backend_mut().queue(LeaveAlternateScreen)?;
     terminal.backend_mut().flush()?;
     disable_raw_mode()?;
@@ -179,15 +272,40 @@ fn cleanup_terminal_force() -> CrosstermResult<()> {
 }
 
 fn tui_main_unmanaged(
-    mut tui: Box<dyn Container>,
+    tui: Box<dyn Container>,
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
 ) -> CrosstermResult<String> {
+    let mut tui = Root::new(tui)
+        .hotkey(
+            KeyBinding::from(KeyCode::Char('q')),
+            HotkeyPriority::After,
+            HotkeyAction::Action(String::from("quit")),
+        )
+        .keymap(vim_window_keymap())
+        .navigation_keymap(Keymap::new().with_binding(
+            vec![KeyBinding::from(KeyCode::Char('r'))],
+            RootCommand::ResizeMode,
+        ))
+        .focus_previous_key(Some(KeyBinding::new(KeyCode::Char('p'), KeyModifiers::ALT)))
+        .quick_jump_key(Some(KeyBinding::new(KeyCode::Char('j'), KeyModifiers::ALT)))
+        .prefix_key(Some(KeyBinding::new(
+            KeyCode::Char('b'),
+            KeyModifiers::CONTROL,
+        )))
+        .split_factory(Some(Box::new(|_| {
+            Component::new(
+                String::from("split"),
+                1,
+                Box::new(TestComponentWidget::new(true)),
+            )
+        })));
     let (tx_input, rx_input) = unbounded();
     let mut last_buffer: Option<Buffer> = None;
     spawn_input_listener(tx_input);
 
     loop {
         let frame_start = Instant::now();
+        tui.handle_update();
 
         // Check if the last buffer can be reused
         if let Some(mut last_buffer) = last_buffer {
@@ -201,14 +319,11 @@ fn tui_main_unmanaged(
 
         // Render the next frame
         let next_frame = terminal.draw(|frame| {
-            if let Err(err) = tui
-                .as_base_mut()
-                .resize(frame.size().width, frame.size().height)
-            {
+            if let Err(err) = tui.resize(frame.size().width, frame.size().height) {
                 panic!("Resizing Error! ({err:?})");
             }
             frame.render_stateful_widget(
-                ComponentBaseWidget::from(tui.as_base_mut()),
+                ComponentBaseWidget::from(&mut tui as &mut dyn ComponentBase),
                 frame.size(),
                 &mut (),
             );
@@ -219,24 +334,25 @@ fn tui_main_unmanaged(
         while !rx_input.is_empty() {
             match rx_input.recv().unwrap() {
                 CrosstermEvent::Key(key) => {
-                    if key.clone().code == KeyCode::Char('q') {
+                    if tui.handle_key(key) == EventResult::Action(String::from("quit")) {
                         done_msg = Some(String::from("User quit!"));
-                    } else {
-                        tui.as_base_mut().handle_key(key);
                     }
                 }
-                CrosstermEvent::Mouse(event) => {
-                    tui.as_base_mut()
-                        .handle_mouse(event.column, event.row, Some(event.kind));
+                event @ CrosstermEvent::Mouse(_) => {
+                    if tui.handle_input(event) == EventResult::Action(String::from("quit")) {
+                        done_msg = Some(String::from("User quit!"));
+                    }
                 }
                 CrosstermEvent::Resize(columns, rows) => {
-                    if let Err(err) = tui.as_base_mut().resize(rows, columns) {
+                    if let Err(err) = tui.resize(rows, columns) {
                         panic!("Resizing Error! ({err:?})");
                     }
                 }
-                CrosstermEvent::FocusGained
+                event @ (CrosstermEvent::FocusGained
                 | CrosstermEvent::FocusLost
-                | CrosstermEvent::Paste(_) => {}
+                | CrosstermEvent::Paste(_)) => {
+                    tui.handle_input(event);
+                }
             }
         }
 
@@ -261,6 +377,7 @@ use backtrace::Backtrace;
 use std::cell::RefCell;
 
 thread_local! {
+    #[allow(clippy::missing_const_for_thread_local)]
     static BACKTRACE: RefCell<Option<Backtrace>> = RefCell::new(None);
 }
 
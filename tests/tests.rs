@@ -1,21 +1,38 @@
 pub mod interactive;
 
 use crossterm::event::{
-    KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseButton, MouseEventKind,
+    Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers,
+    MouseButton, MouseEvent, MouseEventKind,
+};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use tui::{
+    layout::{Direction, Rect},
+    style::{Color, Modifier},
 };
-use tui::layout::Direction;
 use tui_tiling::{
-    component::{Component, ComponentBase},
+    component::{
+        simple::ComponentWidgetSimple, Component, ComponentBase, FocusPolicy, ScrollPolicy,
+    },
+    container::accordion::ContainerAccordion,
+    container::auto::{AutoLayout, ContainerAuto},
     container::list::ContainerList,
+    container::scroll::ContainerScroll,
     container::search::ContainerSearch,
-    container::Container,
+    container::virtual_list::{ChildSource, ContainerVirtual},
+    container::{Container, ContainerChild},
+    keymap::{KeyMatch, Keymap},
     pos::ComponentPos,
-    Border, FocusResult,
+    root::{vim_window_keymap, DropZone, HotkeyAction, HotkeyPriority, Root, RootCommand},
+    Border, EventResult, Focus, FocusResult, KeyBinding, KeyHandler, MenuItem,
 };
 
 use crate::interactive::*;
 
 #[test]
+#[allow(clippy::iter_nth_zero, clippy::redundant_pattern_matching)]
 fn test_tui_tiling() -> Result<(), tui_tiling::ResizeError> {
     let mut component_a = Component::new(
         String::from("a"),
@@ -300,7 +317,2158 @@ fn test_tui_tiling() -> Result<(), tui_tiling::ResizeError> {
     Ok(())
 }
 
+fn key(code: KeyCode) -> KeyEvent {
+    key_modifiers(code, KeyModifiers::empty())
+}
+
+fn key_modifiers(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent {
+        code,
+        modifiers,
+        kind: KeyEventKind::Press,
+        state: KeyEventState::empty(),
+    }
+}
+
+fn focused_name(tui: &dyn Container) -> Option<String> {
+    match tui.search_focused() {
+        FocusResult::Focus((comp, _)) | FocusResult::PartialFocus((comp, _)) => {
+            Some(comp.get_name())
+        }
+        FocusResult::None => None,
+    }
+}
+
+#[test]
+fn test_tui_focusable() -> Result<(), tui_tiling::ResizeError> {
+    let mut tui = ContainerList::new(String::from("list"), Direction::Vertical, true, 0, 0)
+        .from_children(vec![
+            ContainerChild::from(
+                Component::new(
+                    String::from("header"),
+                    1,
+                    Box::new(TestComponentWidget::new(false)),
+                )
+                .fixed_height(Some(3))
+                .focusable(false),
+            ),
+            ContainerChild::from(Component::new(
+                String::from("a"),
+                1,
+                Box::new(TestComponentWidget::new(false)),
+            )),
+            ContainerChild::from(
+                Component::new(
+                    String::from("separator"),
+                    0,
+                    Box::new(TestComponentWidget::new(false)),
+                )
+                .fixed_height(Some(1))
+                .focusable(false),
+            ),
+            ContainerChild::from(
+                Component::new(
+                    String::from("b"),
+                    1,
+                    Box::new(TestComponentWidget::new(false)),
+                )
+                .disabled(true),
+            ),
+            ContainerChild::from(Component::new(
+                String::from("c"),
+                1,
+                Box::new(TestComponentWidget::new(false)),
+            )),
+        ])?;
+    tui.resize(10, 16)?;
+
+    // Clicking a non-focusable component does not focus it
+    tui.handle_mouse(1, 1, Some(MouseEventKind::Down(MouseButton::Left)));
+    assert_eq!(focused_name(tui.as_container()), None);
+
+    // Enter with nothing focused skips the header
+    tui.handle_key(key(KeyCode::Enter));
+    assert_eq!(focused_name(tui.as_container()), Some(String::from("a")));
+
+    // Moving down skips the separator and the disabled component
+    tui.handle_key(key(KeyCode::Down));
+    assert_eq!(focused_name(tui.as_container()), Some(String::from("c")));
+
+    // Moving up does the same, and stops at the top
+    tui.handle_key(key(KeyCode::Up));
+    assert_eq!(focused_name(tui.as_container()), Some(String::from("a")));
+    tui.handle_key(key(KeyCode::Up));
+    assert_eq!(focused_name(tui.as_container()), Some(String::from("a")));
+
+    // Disabled components ignore clicks and keys
+    let (_, pos) = tui.as_container().search_name("b").unwrap();
+    tui.handle_mouse(
+        pos.x + 1,
+        pos.y + 1,
+        Some(MouseEventKind::Down(MouseButton::Left)),
+    );
+    assert_eq!(focused_name(tui.as_container()), None);
+    let widget = tui
+        .as_container()
+        .search_name_widget::<TestComponentWidget>("b")
+        .unwrap();
+    assert!(widget.get_mouse_last().is_none());
+    assert!(widget.get_key_last().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_focus_history() -> Result<(), tui_tiling::ResizeError> {
+    let mut tui = Root::new(get_tui(false)?)
+        .focus_previous_key(Some(KeyBinding::new(KeyCode::Tab, KeyModifiers::ALT)));
+    tui.resize(32, 16)?;
+
+    tui.handle_key(key(KeyCode::Enter));
+    tui.handle_key(key(KeyCode::Down));
+    tui.handle_key(key(KeyCode::Down));
+    tui.handle_key(key(KeyCode::Right));
+    assert_eq!(
+        tui.get_focus_history(),
+        vec!["c", "vertical.b", "vertical.a", "vertical.fixed"]
+    );
+
+    // Jump back and forth between the two most recent components
+    tui.handle_key(key_modifiers(KeyCode::Tab, KeyModifiers::ALT));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.b"))
+    );
+    assert!(tui.focus_previous());
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("c"))
+    );
+
+    // Walk further back into the history
+    assert!(tui.focus_history(2));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.a"))
+    );
+    assert!(!tui.focus_history(4));
+
+    // Removing the focused component falls back to the most recent survivor
+    assert!(tui.remove("vertical.a").is_some());
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("c"))
+    );
+    assert_eq!(
+        tui.get_focus_history(),
+        vec!["c", "vertical.b", "vertical.fixed"]
+    );
+
+    // Disabling the focused component does the same
+    tui.get_container_mut()
+        .search_name_mut("c")
+        .unwrap()
+        .0
+        .unwrap_component_mut()
+        .set_disabled(true);
+    tui.handle_update();
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.b"))
+    );
+
+    // Removing a component without focus drops it from the history too
+    assert!(tui.remove("vertical.fixed").is_some());
+    assert_eq!(tui.get_focus_history(), vec!["vertical.b"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_quick_jump() -> Result<(), tui_tiling::ResizeError> {
+    let mut tui = Root::new(get_tui(false)?).quick_jump_key(Some(KeyBinding::new(
+        KeyCode::Char('j'),
+        KeyModifiers::CONTROL,
+    )));
+    tui.resize(32, 16)?;
+
+    tui.handle_key(key_modifiers(KeyCode::Char('j'), KeyModifiers::CONTROL));
+    assert_eq!(
+        tui.get_quick_jump_labels(),
+        vec![
+            (String::from("1"), String::from("vertical.fixed")),
+            (String::from("2"), String::from("vertical.a")),
+            (String::from("3"), String::from("vertical.b")),
+            (String::from("4"), String::from("c")),
+        ]
+    );
+
+    // Labels are drawn in the middle of each component
+    let buffer = render_helper(&mut tui);
+    assert_eq!(buffer.get(23, 7).symbol, "4");
+    assert_eq!(buffer.get(7, 13).symbol, "3");
+
+    // Typing a label focuses the component directly
+    tui.handle_key(key(KeyCode::Char('3')));
+    assert!(tui.get_quick_jump_labels().is_empty());
+    match tui.get_container().search_focused() {
+        FocusResult::Focus((comp, _)) => assert_eq!(comp.get_name(), String::from("b")),
+        _ => panic!("Component b should be focused!"),
+    }
+
+    // Any other key cancels without changing focus
+    tui.handle_key(key_modifiers(KeyCode::Char('j'), KeyModifiers::CONTROL));
+    tui.handle_key(key(KeyCode::Esc));
+    assert!(tui.get_quick_jump_labels().is_empty());
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.b"))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_prefix() -> Result<(), tui_tiling::ResizeError> {
+    let prefix = key_modifiers(KeyCode::Char('b'), KeyModifiers::CONTROL);
+    let mut tui = Root::new(get_tui(false)?)
+        .prefix_key(Some(KeyBinding::new(
+            KeyCode::Char('b'),
+            KeyModifiers::CONTROL,
+        )))
+        .split_factory(Some(Box::new(|_| {
+            Component::new(
+                String::from("new"),
+                1,
+                Box::new(TestComponentWidget::new(false)),
+            )
+        })));
+    tui.resize(32, 16)?;
+    tui.handle_key(key(KeyCode::Enter));
+    tui.handle_key(key(KeyCode::Enter));
+
+    // Prefix is shown while waiting for a command
+    tui.handle_key(prefix);
+    assert!(tui.is_prefix_pending());
+    assert_eq!(tui.get_status(), Some(String::from("PREFIX")));
+    let buffer = render_helper(&mut tui);
+    assert_eq!(buffer.get(31, 15).symbol, " ");
+    assert_eq!(buffer.get(30, 15).symbol, "X");
+
+    // Commands move focus without the focused widget seeing any keys
+    tui.handle_key(key(KeyCode::Down));
+    assert!(!tui.is_prefix_pending());
+    match tui.get_container().search_focused() {
+        FocusResult::Focus((comp, _)) => assert_eq!(comp.get_name(), String::from("a")),
+        _ => panic!("Component a should be focused!"),
+    }
+    assert!(tui
+        .get_container()
+        .search_name_widget::<TestComponentWidget>("vertical.fixed")
+        .unwrap()
+        .get_key_last()
+        .is_none());
+
+    // Pressing the prefix twice sends it to the focused widget
+    tui.handle_key(prefix);
+    tui.handle_key(prefix);
+    assert_eq!(
+        tui.get_container()
+            .search_name_widget::<TestComponentWidget>("vertical.a")
+            .unwrap()
+            .get_key_last(),
+        Some(prefix)
+    );
+
+    // Splitting creates a new focused component next to the old one
+    tui.handle_key(prefix);
+    tui.handle_key(key(KeyCode::Char('%')));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.a_split.new"))
+    );
+    let (child, pos) = tui
+        .get_container()
+        .search_name("vertical.a_split.new")
+        .unwrap();
+    assert_eq!(pos, ComponentPos { x: 8, y: 6 });
+    assert_eq!(child.as_base().get_width(), 8);
+
+    // Zooming renders only the focused component over the whole root
+    let get_split_rects = |tui: &Root| match tui.get_container().search_name("vertical.a_split") {
+        Some((ContainerChild::Container(split), _)) => split.get_children_rectangles(),
+        _ => panic!("vertical.a_split should be a container!"),
+    };
+    let split_rects = get_split_rects(&tui);
+    tui.handle_key(prefix);
+    tui.handle_key(key(KeyCode::Char('z')));
+    assert_eq!(tui.get_zoomed(), Some(String::from("vertical.a_split.new")));
+    let buffer = render_helper(&mut tui);
+    assert_eq!(buffer.get(0, 0).symbol, "╭");
+    assert_eq!(buffer.get(1, 0).symbol, "n");
+    assert_eq!(buffer.get(31, 15).symbol, "╯");
+    tui.handle_key(prefix);
+    tui.handle_key(key(KeyCode::Char('z')));
+    assert_eq!(tui.get_zoomed(), None);
+    let (child, _) = tui
+        .get_container()
+        .search_name("vertical.a_split.new")
+        .unwrap();
+    assert_eq!(child.as_base().get_width(), 8);
+    assert_eq!(get_split_rects(&tui), split_rects);
+
+    // Swapping and resizing have default bindings too
+    tui.handle_key(prefix);
+    tui.handle_key(key(KeyCode::Char('{')));
+    let (_, pos) = tui
+        .get_container()
+        .search_name("vertical.a_split.new")
+        .unwrap();
+    assert_eq!(pos, ComponentPos { x: 0, y: 6 });
+    tui.handle_key(prefix);
+    tui.handle_key(key_modifiers(KeyCode::Right, KeyModifiers::CONTROL));
+    let (child, _) = tui
+        .get_container()
+        .search_name("vertical.a_split.new")
+        .unwrap();
+    assert_eq!(child.as_base().get_width(), 9);
+
+    // Closing removes the component and focuses the previous one
+    tui.handle_key(prefix);
+    tui.handle_key(key(KeyCode::Char('x')));
+    assert!(tui
+        .get_container()
+        .search_name("vertical.a_split.new")
+        .is_none());
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.a_split.a"))
+    );
+
+    // Prefix is cancelled after the timeout
+    tui.set_prefix_timeout(Duration::ZERO);
+    tui.handle_key(prefix);
+    std::thread::sleep(Duration::from_millis(1));
+    tui.handle_update();
+    assert!(!tui.is_prefix_pending());
+    assert_eq!(tui.get_status(), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_keymap() {
+    let mut keymap = Keymap::new()
+        .with_binding(
+            vec![KeyBinding::from(KeyCode::Char('g'))],
+            String::from("g"),
+        )
+        .with_binding(
+            vec![
+                KeyBinding::from(KeyCode::Char('g')),
+                KeyBinding::from(KeyCode::Char('g')),
+            ],
+            String::from("gg"),
+        )
+        .with_binding(
+            vec![
+                KeyBinding::from(KeyCode::Char('z')),
+                KeyBinding::from(KeyCode::Char('z')),
+            ],
+            String::from("zz"),
+        )
+        .timeout(Duration::ZERO);
+
+    // Longer binding is preferred while it can still match
+    assert_eq!(keymap.handle_key(key(KeyCode::Char('g'))), vec![]);
+    assert_eq!(
+        keymap.get_completions(),
+        vec![(
+            vec![KeyBinding::from(KeyCode::Char('g'))],
+            String::from("gg")
+        )]
+    );
+    assert_eq!(
+        keymap.handle_key(key(KeyCode::Char('g'))),
+        vec![KeyMatch::Binding(String::from("gg"))]
+    );
+
+    // Shorter binding is used when the sequence is broken
+    keymap.handle_key(key(KeyCode::Char('g')));
+    assert_eq!(
+        keymap.handle_key(key(KeyCode::Char('x'))),
+        vec![
+            KeyMatch::Binding(String::from("g")),
+            KeyMatch::Key(key(KeyCode::Char('x')))
+        ]
+    );
+
+    // Shorter binding is used when the timeout passes
+    keymap.handle_key(key(KeyCode::Char('g')));
+    std::thread::sleep(Duration::from_millis(1));
+    assert_eq!(
+        keymap.handle_timeout(),
+        vec![KeyMatch::Binding(String::from("g"))]
+    );
+
+    // Held keys are given back if nothing matches
+    keymap.handle_key(key(KeyCode::Char('z')));
+    assert!(keymap.is_pending());
+    assert_eq!(
+        keymap.handle_key(key(KeyCode::Char('a'))),
+        vec![
+            KeyMatch::Key(key(KeyCode::Char('z'))),
+            KeyMatch::Key(key(KeyCode::Char('a')))
+        ]
+    );
+    assert!(!keymap.is_pending());
+}
+
+#[test]
+fn test_tui_keymap_root() -> Result<(), tui_tiling::ResizeError> {
+    let ctrl_w = key_modifiers(KeyCode::Char('w'), KeyModifiers::CONTROL);
+    let mut tui = Root::new(get_tui(false)?)
+        .keymap(vim_window_keymap())
+        .navigation_keymap(
+            Keymap::new()
+                .with_binding(
+                    vec![KeyBinding::from(KeyCode::Char('j'))],
+                    RootCommand::FocusMove(Border::Bottom),
+                )
+                .with_binding(
+                    vec![KeyBinding::from(KeyCode::Char('k'))],
+                    RootCommand::FocusMove(Border::Top),
+                ),
+        );
+    tui.resize(32, 16)?;
+
+    // Navigation bindings work while moving around with partial focus
+    tui.handle_key(key(KeyCode::Enter));
+    tui.handle_key(key(KeyCode::Char('j')));
+    tui.handle_key(key(KeyCode::Char('j')));
+    tui.handle_key(key(KeyCode::Char('k')));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.a"))
+    );
+
+    // But not once a component has full focus
+    tui.handle_key(key(KeyCode::Enter));
+    tui.handle_key(key(KeyCode::Char('j')));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.a"))
+    );
+    let widget_key = |tui: &Root| {
+        tui.get_container()
+            .search_name_widget::<TestComponentWidget>("vertical.a")
+            .unwrap()
+            .get_key_last()
+    };
+    assert_eq!(widget_key(&tui), Some(key(KeyCode::Char('j'))));
+
+    // Window commands work from anywhere, with the pending keys shown
+    tui.handle_key(ctrl_w);
+    assert_eq!(tui.get_status(), Some(String::from("C-w")));
+    assert_eq!(tui.get_completions().len(), 17);
+    tui.handle_key(key(KeyCode::Char('l')));
+    assert_eq!(tui.get_status(), None);
+    match tui.get_container().search_focused() {
+        FocusResult::Focus((comp, _)) => assert_eq!(comp.get_name(), String::from("c")),
+        _ => panic!("Component c should be focused!"),
+    }
+
+    // Shifted characters match bindings without shift, and releases neither
+    // run bindings nor break up a sequence
+    let release = |mut e: KeyEvent| {
+        e.kind = KeyEventKind::Release;
+        e
+    };
+    let rotate = KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT);
+    tui.handle_key(ctrl_w);
+    tui.handle_key(release(ctrl_w));
+    tui.handle_key(rotate);
+    tui.handle_key(release(rotate));
+    assert_eq!(tui.get_status(), None);
+    let (_, pos) = tui.get_container().search_name("c").unwrap();
+    assert_eq!(pos, ComponentPos { x: 0, y: 0 });
+
+    // Unfinished sequences are given to the focused widget after the timeout
+    tui.get_keymap_mut().set_timeout(Duration::ZERO);
+    tui.handle_key(ctrl_w);
+    std::thread::sleep(Duration::from_millis(1));
+    tui.handle_update();
+    assert_eq!(
+        tui.get_container()
+            .search_name_widget::<TestComponentWidget>("c")
+            .unwrap()
+            .get_key_last(),
+        Some(ctrl_w)
+    );
+
+    Ok(())
+}
+
+fn key_action(code: char, action: &'static str) -> Option<KeyHandler> {
+    Some(Box::new(move |e: KeyEvent| {
+        if e.code == KeyCode::Char(code) {
+            EventResult::Action(String::from(action))
+        } else {
+            EventResult::Ignored
+        }
+    }))
+}
+
+#[test]
+fn test_tui_event_result() -> Result<(), tui_tiling::ResizeError> {
+    let mut tui = ContainerList::new(
+        String::from("horizontal"),
+        Direction::Horizontal,
+        true,
+        0,
+        0,
+    )
+    .key_handler(key_action('q', "quit"))
+    .from_children(vec![
+        ContainerChild::from(
+            ContainerList::new(String::from("vertical"), Direction::Vertical, true, 0, 0)
+                .key_handler(key_action('x', "close"))
+                .from_children(vec![
+                    ContainerChild::from(Component::new(
+                        String::from("a"),
+                        1,
+                        Box::new(ComponentWidgetSimple::new()),
+                    )),
+                    ContainerChild::from(Component::new(
+                        String::from("b"),
+                        1,
+                        Box::new(TestComponentWidget::new(false)),
+                    )),
+                ])?,
+        ),
+        ContainerChild::from(Component::new(
+            String::from("c"),
+            1,
+            Box::new(ComponentWidgetSimple::new()),
+        )),
+    ])?;
+    tui.resize(32, 16)?;
+
+    // Keys ignored by the widget bubble up through each container
+    assert_eq!(tui.handle_key(key(KeyCode::Enter)), EventResult::Redraw);
+    assert_eq!(tui.handle_key(key(KeyCode::Enter)), EventResult::Redraw);
+    assert_eq!(
+        tui.handle_key(key(KeyCode::Char('x'))),
+        EventResult::Action(String::from("close"))
+    );
+    assert_eq!(
+        tui.handle_key(key(KeyCode::Char('q'))),
+        EventResult::Action(String::from("quit"))
+    );
+    assert_eq!(
+        tui.handle_key(key(KeyCode::Char('z'))),
+        EventResult::Ignored
+    );
+
+    // Keys used by the widget do not bubble up
+    tui.handle_key(key(KeyCode::Esc));
+    assert_eq!(tui.handle_key(key(KeyCode::Down)), EventResult::Redraw);
+    tui.handle_key(key(KeyCode::Enter));
+    assert_eq!(tui.handle_key(key(KeyCode::Char('q'))), EventResult::Redraw);
+
+    // Only the containers enclosing the focused component see the key
+    tui.handle_key(key(KeyCode::Esc));
+    tui.handle_key(key(KeyCode::Right));
+    assert_eq!(
+        tui.handle_key(key(KeyCode::Char('x'))),
+        EventResult::Ignored
+    );
+    assert_eq!(
+        tui.handle_key(key(KeyCode::Char('q'))),
+        EventResult::Action(String::from("quit"))
+    );
+
+    // Moving past the edge of the root passes the border up
+    assert_eq!(
+        tui.handle_key(key(KeyCode::Right)),
+        EventResult::Border(Border::Right)
+    );
+    assert_eq!(focused_name(tui.as_container()), Some(String::from("c")));
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_hotkeys() -> Result<(), tui_tiling::ResizeError> {
+    let count = Rc::new(Cell::new(0));
+    let counter = count.clone();
+    let mut tui = Root::new(get_tui(false)?)
+        .hotkey(
+            KeyBinding::from(KeyCode::Char('q')),
+            HotkeyPriority::After,
+            HotkeyAction::Action(String::from("quit")),
+        )
+        .hotkey(
+            KeyBinding::from(KeyCode::F(1)),
+            HotkeyPriority::Before,
+            HotkeyAction::Action(String::from("help")),
+        )
+        .hotkey(
+            KeyBinding::new(KeyCode::Char('n'), KeyModifiers::CONTROL),
+            HotkeyPriority::Before,
+            HotkeyAction::Callback(Box::new(move |_| {
+                counter.set(counter.get() + 1);
+                EventResult::Redraw
+            })),
+        );
+    tui.resize(32, 16)?;
+
+    // Low priority hotkeys only fire when nothing else uses the key
+    assert_eq!(
+        tui.handle_key(key(KeyCode::Char('q'))),
+        EventResult::Action(String::from("quit"))
+    );
+    tui.handle_key(key(KeyCode::Enter));
+    tui.handle_key(key(KeyCode::Enter));
+    assert_eq!(tui.handle_key(key(KeyCode::Char('q'))), EventResult::Redraw);
+    let widget = tui
+        .get_container()
+        .search_name_widget::<TestComponentWidget>("vertical.fixed")
+        .unwrap();
+    assert_eq!(widget.get_key_last(), Some(key(KeyCode::Char('q'))));
+
+    // High priority hotkeys fire before the focused widget sees the key
+    assert_eq!(
+        tui.handle_key(key(KeyCode::F(1))),
+        EventResult::Action(String::from("help"))
+    );
+    assert_eq!(
+        tui.handle_key(key_modifiers(KeyCode::Char('n'), KeyModifiers::CONTROL)),
+        EventResult::Redraw
+    );
+    assert_eq!(count.get(), 1);
+    let widget = tui
+        .get_container()
+        .search_name_widget::<TestComponentWidget>("vertical.fixed")
+        .unwrap();
+    assert_eq!(widget.get_key_last(), Some(key(KeyCode::Char('q'))));
+
+    // Removed hotkeys fall through to the widget
+    tui.remove_hotkey(&KeyBinding::from(KeyCode::F(1)));
+    assert_eq!(tui.handle_key(key(KeyCode::F(1))), EventResult::Redraw);
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_paste_and_terminal_focus() -> Result<(), tui_tiling::ResizeError> {
+    let mut tui = Root::new(get_tui(false)?);
+    tui.resize(32, 16)?;
+
+    // Pasted text only goes to a fully focused component
+    let paste = || CrosstermEvent::Paste(String::from("pasted"));
+    assert_eq!(tui.handle_input(paste()), EventResult::Ignored);
+    tui.handle_key(key(KeyCode::Enter));
+    assert_eq!(tui.handle_input(paste()), EventResult::Ignored);
+    tui.handle_key(key(KeyCode::Enter));
+    assert_eq!(tui.handle_input(paste()), EventResult::Redraw);
+    let widget = tui
+        .get_container()
+        .search_name_widget::<TestComponentWidget>("vertical.fixed")
+        .unwrap();
+    assert_eq!(widget.get_paste_last(), Some(String::from("pasted")));
+    let widget = tui
+        .get_container()
+        .search_name_widget::<TestComponentWidget>("c")
+        .unwrap();
+    assert_eq!(widget.get_paste_last(), None);
+
+    // Terminal focus changes reach every component and dim the borders
+    tui.handle_input(CrosstermEvent::FocusLost);
+    for path in ["vertical.fixed", "vertical.a", "vertical.b", "c"] {
+        let widget = tui
+            .get_container()
+            .search_name_widget::<TestComponentWidget>(path)
+            .unwrap();
+        assert!(!widget.is_terminal_focused());
+    }
+    let buffer = render_helper(&mut tui);
+    assert!(buffer.get(0, 0).modifier.contains(Modifier::DIM));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.fixed"))
+    );
+
+    tui.handle_input(CrosstermEvent::FocusGained);
+    let widget = tui
+        .get_container()
+        .search_name_widget::<TestComponentWidget>("c")
+        .unwrap();
+    assert!(widget.is_terminal_focused());
+    let buffer = render_helper(&mut tui);
+    assert!(!buffer.get(0, 0).modifier.contains(Modifier::DIM));
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_hover() -> Result<(), tui_tiling::ResizeError> {
+    let mut tui = Root::new(get_tui(false)?);
+    tui.resize(32, 16)?;
+    let hovered = |tui: &Root, path: &str| {
+        tui.get_container()
+            .search_name_widget::<TestComponentWidget>(path)
+            .unwrap()
+            .is_hovered()
+    };
+    let (_, pos_a) = tui.get_container().search_name("vertical.a").unwrap();
+    let (_, pos_c) = tui.get_container().search_name("c").unwrap();
+
+    // Moving over a component delivers the move without focusing it
+    tui.handle_mouse(pos_a.x + 2, pos_a.y + 2, Some(MouseEventKind::Moved));
+    assert!(hovered(&tui, "vertical.a"));
+    assert!(!hovered(&tui, "c"));
+    let widget = tui
+        .get_container()
+        .search_name_widget::<TestComponentWidget>("vertical.a")
+        .unwrap();
+    assert_eq!(widget.get_mouse_last(), Some((1, 1, MouseEventKind::Moved)));
+    assert!(matches!(
+        tui.get_container().search_focused(),
+        FocusResult::None
+    ));
+
+    // Moving to another component leaves the first one
+    tui.handle_mouse(pos_c.x + 2, pos_c.y + 2, Some(MouseEventKind::Moved));
+    assert!(!hovered(&tui, "vertical.a"));
+    assert!(hovered(&tui, "c"));
+
+    // Borders under the mouse are highlighted
+    tui.handle_mouse(pos_c.x, pos_c.y + 2, Some(MouseEventKind::Moved));
+    let Some((ContainerChild::Component(c), _)) = tui.get_container().search_name("c") else {
+        panic!("c is not a component");
+    };
+    assert_eq!(c.get_hover_border(), Some(Border::Left));
+    let buffer = render_helper(&mut tui);
+    assert_eq!(buffer.get(pos_c.x, pos_c.y + 2).fg, Color::LightCyan);
+    assert_ne!(buffer.get(pos_c.x + 1, pos_c.y).fg, Color::LightCyan);
+
+    // Leaving the root clears all hover state
+    tui.handle_mouse_leave();
+    assert!(!hovered(&tui, "c"));
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_scroll_policy() -> Result<(), tui_tiling::ResizeError> {
+    let mut tui = Root::new(get_tui(false)?);
+    tui.resize(32, 16)?;
+    let scroll = |x: u16, y: u16, modifiers: KeyModifiers| {
+        CrosstermEvent::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: x,
+            row: y,
+            modifiers,
+        })
+    };
+    let scrolled = |tui: &Root, path: &str| {
+        tui.get_container()
+            .search_name_widget::<TestComponentWidget>(path)
+            .unwrap()
+            .get_mouse_last()
+            .is_some()
+    };
+    let (_, pos_a) = tui.get_container().search_name("vertical.a").unwrap();
+    let (_, pos_c) = tui.get_container().search_name("c").unwrap();
+    tui.handle_key(key(KeyCode::Enter));
+    tui.handle_key(key(KeyCode::Enter));
+
+    // By default scrolling goes to the component under the mouse
+    tui.handle_input(scroll(pos_a.x + 2, pos_a.y + 2, KeyModifiers::NONE));
+    assert!(scrolled(&tui, "vertical.a"));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.fixed"))
+    );
+
+    // Components can require focus to scroll
+    if let Some((ContainerChild::Component(c), _)) = tui.get_container_mut().search_name_mut("c") {
+        c.set_scroll_policy(ScrollPolicy::Focused);
+    }
+    tui.handle_input(scroll(pos_c.x + 2, pos_c.y + 2, KeyModifiers::NONE));
+    assert!(!scrolled(&tui, "c"));
+
+    // Components can take focus when scrolled unless the modifier is held
+    if let Some((ContainerChild::Component(b), _)) =
+        tui.get_container_mut().search_name_mut("vertical.b")
+    {
+        b.set_scroll_policy(ScrollPolicy::FocusHovered(KeyModifiers::ALT));
+    }
+    let (_, pos_b) = tui.get_container().search_name("vertical.b").unwrap();
+    tui.handle_input(scroll(pos_b.x + 2, pos_b.y + 2, KeyModifiers::ALT));
+    assert!(scrolled(&tui, "vertical.b"));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.fixed"))
+    );
+    tui.handle_input(scroll(pos_b.x + 2, pos_b.y + 2, KeyModifiers::NONE));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.b"))
+    );
+    assert_eq!(tui.get_container().get_focus(), Focus::Focus);
+
+    // Without a modifier every scroll takes focus, even with modifiers held
+    if let Some((ContainerChild::Component(a), _)) =
+        tui.get_container_mut().search_name_mut("vertical.a")
+    {
+        a.set_scroll_policy(ScrollPolicy::FocusHovered(KeyModifiers::NONE));
+    }
+    tui.handle_input(scroll(pos_a.x + 2, pos_a.y + 2, KeyModifiers::CONTROL));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.a"))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_drag_capture() -> Result<(), tui_tiling::ResizeError> {
+    let mut tui = Root::new(get_tui(false)?);
+    tui.resize(32, 16)?;
+    let widget = |tui: &Root, path: &str| {
+        let widget = tui
+            .get_container()
+            .search_name_widget::<TestComponentWidget>(path)
+            .unwrap();
+        (widget.get_mouse_last(), widget.get_mouse_captured_last())
+    };
+    let (_, pos_a) = tui.get_container().search_name("vertical.a").unwrap();
+    let (_, pos_c) = tui.get_container().search_name("c").unwrap();
+    let (ax, ay) = (pos_a.x + 2, pos_a.y + 2);
+    tui.handle_mouse(ax, ay, Some(MouseEventKind::Down(MouseButton::Left)));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.a"))
+    );
+
+    // Drags inside the widget are delivered normally
+    let drag = MouseEventKind::Drag(MouseButton::Left);
+    tui.handle_mouse(ax + 1, ay, Some(drag));
+    assert_eq!(widget(&tui, "vertical.a").0, Some((2, 1, drag)));
+
+    // Drags outside of the widget stay with it using relative positions
+    tui.handle_mouse(0, 0, Some(drag));
+    let (x, y) = (-(pos_a.x as i32) - 1, -(pos_a.y as i32) - 1);
+    assert_eq!(widget(&tui, "vertical.a").1, Some((x, y, drag)));
+    tui.handle_mouse(pos_c.x + 2, pos_c.y + 2, Some(drag));
+    assert_eq!(widget(&tui, "c"), (None, None));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.a"))
+    );
+
+    // Releasing ends the capture
+    let up = MouseEventKind::Up(MouseButton::Left);
+    tui.handle_mouse(pos_c.x + 2, pos_c.y + 2, Some(up));
+    let x = (pos_c.x - pos_a.x) as i32 + 1;
+    let y = (pos_c.y as i32 - pos_a.y as i32) + 1;
+    assert_eq!(widget(&tui, "vertical.a").1, Some((x, y, up)));
+    tui.handle_mouse(0, 0, Some(drag));
+    assert_eq!(widget(&tui, "vertical.a").1, Some((x, y, up)));
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_click_count() -> Result<(), tui_tiling::ResizeError> {
+    let mut tui = Root::new(get_tui(false)?).click_distance(1);
+    tui.resize(32, 16)?;
+    let click = |tui: &mut Root, x: u16, y: u16, button: MouseButton| {
+        tui.handle_mouse(x, y, Some(MouseEventKind::Down(button)));
+        tui.handle_mouse(x, y, Some(MouseEventKind::Up(button)));
+    };
+    let count = |tui: &Root, path: &str| {
+        tui.get_container()
+            .search_name_widget::<TestComponentWidget>(path)
+            .unwrap()
+            .get_click_last()
+            .map(|(_, _, _, count)| count)
+    };
+    let (_, pos_a) = tui.get_container().search_name("vertical.a").unwrap();
+    let (_, pos_c) = tui.get_container().search_name("c").unwrap();
+    let (ax, ay) = (pos_a.x + 2, pos_a.y + 2);
+
+    // Clicks close together in time and space are counted
+    click(&mut tui, ax, ay, MouseButton::Left);
+    assert_eq!(count(&tui, "vertical.a"), Some(1));
+    click(&mut tui, ax + 1, ay, MouseButton::Left);
+    assert_eq!(count(&tui, "vertical.a"), Some(2));
+    click(&mut tui, ax + 1, ay + 1, MouseButton::Left);
+    assert_eq!(count(&tui, "vertical.a"), Some(3));
+
+    // Clicks too far apart or with another button start over
+    click(&mut tui, ax + 3, ay + 1, MouseButton::Left);
+    assert_eq!(count(&tui, "vertical.a"), Some(1));
+    click(&mut tui, ax + 3, ay + 1, MouseButton::Middle);
+    assert_eq!(count(&tui, "vertical.a"), Some(1));
+
+    // Clicks too far apart in time start over
+    tui.set_click_timeout(Duration::from_millis(10));
+    std::thread::sleep(Duration::from_millis(20));
+    click(&mut tui, ax + 3, ay + 1, MouseButton::Middle);
+    assert_eq!(count(&tui, "vertical.a"), Some(1));
+
+    // Double clicking a title zooms the component
+    click(&mut tui, pos_c.x + 3, pos_c.y, MouseButton::Left);
+    tui.set_click_timeout(Duration::from_secs(10));
+    click(&mut tui, pos_c.x + 3, pos_c.y, MouseButton::Left);
+    assert_eq!(tui.get_zoomed(), Some(String::from("c")));
+    click(&mut tui, 3, 0, MouseButton::Left);
+    click(&mut tui, 3, 0, MouseButton::Left);
+    assert_eq!(tui.get_zoomed(), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_context_menu() -> Result<(), tui_tiling::ResizeError> {
+    let mut tui = Root::new(get_tui(false)?);
+    tui.resize(32, 16)?;
+    let mouse = |x: u16, y: u16, kind: MouseEventKind| {
+        CrosstermEvent::Mouse(MouseEvent {
+            kind,
+            column: x,
+            row: y,
+            modifiers: KeyModifiers::NONE,
+        })
+    };
+    let right = MouseEventKind::Down(MouseButton::Right);
+    let labels = |tui: &Root| tui.get_menu().map(|(labels, _)| labels);
+    let (_, pos_a) = tui.get_container().search_name("vertical.a").unwrap();
+    let (_, pos_c) = tui.get_container().search_name("c").unwrap();
+    let (ax, ay) = (pos_a.x + 2, pos_a.y + 2);
+
+    // The widget supplies items first, then the containers
+    tui.handle_input(mouse(ax, ay, right));
+    assert_eq!(
+        labels(&tui),
+        Some(vec![String::from("Copy"), String::from("Quit")])
+    );
+    tui.handle_key(key(KeyCode::Down));
+    assert_eq!(tui.get_menu().map(|(_, selected)| selected), Some(1));
+    assert_eq!(
+        tui.handle_key(key(KeyCode::Enter)),
+        EventResult::Action(String::from("quit"))
+    );
+    assert_eq!(tui.get_menu(), None);
+
+    // Clicking a widget item sends the action back to the widget
+    tui.handle_input(mouse(ax, ay, right));
+    let result = tui.handle_input(mouse(
+        ax + 2,
+        ay + 1,
+        MouseEventKind::Down(MouseButton::Left),
+    ));
+    assert_eq!(result, EventResult::Redraw);
+    let widget = tui
+        .get_container()
+        .search_name_widget::<TestComponentWidget>("vertical.a")
+        .unwrap();
+    assert_eq!(widget.get_menu_last(), Some(String::from("copy")));
+    assert_eq!(tui.get_menu(), None);
+
+    // Mouse events passed directly leave the chosen action to be taken, and
+    // it does not replace the result of a later event
+    tui.handle_mouse(ax, ay, Some(right));
+    tui.handle_mouse(
+        ax + 2,
+        ay + 2,
+        Some(MouseEventKind::Down(MouseButton::Left)),
+    );
+    assert_eq!(
+        tui.take_menu_result(),
+        Some(EventResult::Action(String::from("quit")))
+    );
+    assert_eq!(tui.take_menu_result(), None);
+    tui.handle_mouse(ax, ay, Some(right));
+    tui.handle_mouse(
+        ax + 2,
+        ay + 2,
+        Some(MouseEventKind::Down(MouseButton::Left)),
+    );
+    assert_ne!(
+        tui.handle_input(CrosstermEvent::Key(key(KeyCode::Char('x')))),
+        EventResult::Action(String::from("quit"))
+    );
+    assert_eq!(tui.take_menu_result(), None);
+
+    // Borders get the layout commands, which act on that component
+    tui.handle_input(mouse(pos_c.x + 3, pos_c.y, right));
+    assert_eq!(
+        labels(&tui),
+        Some(vec![
+            String::from("Quit"),
+            String::from("Split horizontal"),
+            String::from("Split vertical"),
+            String::from("Close"),
+            String::from("Zoom"),
+        ])
+    );
+    tui.handle_key(key(KeyCode::Up));
+    tui.handle_key(key(KeyCode::Enter));
+    assert_eq!(tui.get_zoomed(), Some(String::from("c")));
+
+    // Escape or clicking elsewhere closes the menu
+    tui.handle_input(mouse(3, 3, right));
+    assert!(tui.get_menu().is_some());
+    tui.handle_key(key(KeyCode::Esc));
+    assert_eq!(tui.get_menu(), None);
+    tui.handle_input(mouse(3, 3, right));
+    tui.handle_input(mouse(20, 12, MouseEventKind::Down(MouseButton::Left)));
+    assert_eq!(tui.get_menu(), None);
+
+    // Menus taller than the root are clipped, and hovering stays on the items
+    let mut tui = Root::new(Box::new(
+        ContainerList::new(String::from("list"), Direction::Horizontal, true, 0, 0).from_children(
+            vec![ContainerChild::from(Component::new(
+                String::from("a"),
+                1,
+                Box::new(TestComponentWidget::new(false)),
+            ))],
+        )?,
+    ));
+    tui.resize(20, 4)?;
+    tui.handle_input(mouse(3, 0, right));
+    assert_eq!(labels(&tui).map(|labels| labels.len()), Some(4));
+    for y in 0..4 {
+        tui.handle_input(mouse(5, y, MouseEventKind::Moved));
+        let (labels, selected) = tui.get_menu().unwrap();
+        assert!(selected < labels.len());
+    }
+    assert_eq!(tui.get_menu().map(|(_, selected)| selected), Some(1));
+    let buffer = render_helper(&mut tui);
+    assert_eq!(buffer.get(0, 3).symbol, "╰");
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_focus_policy() -> Result<(), tui_tiling::ResizeError> {
+    let mut tui = Root::new(get_tui(false)?).focus_policy(Some(FocusPolicy::ClickToFocus));
+    tui.resize(32, 16)?;
+    let down = MouseEventKind::Down(MouseButton::Left);
+    let widget = |tui: &Root, path: &str| {
+        let widget = tui
+            .get_container()
+            .search_name_widget::<TestComponentWidget>(path)
+            .unwrap();
+        (widget.get_mouse_last(), widget.get_click_last())
+    };
+    let (_, pos_a) = tui.get_container().search_name("vertical.a").unwrap();
+    let (_, pos_c) = tui.get_container().search_name("c").unwrap();
+    let (ax, ay) = (pos_a.x + 2, pos_a.y + 2);
+
+    // The first click only focuses the component
+    tui.handle_mouse(ax, ay, Some(down));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.a"))
+    );
+    assert_eq!(widget(&tui, "vertical.a"), (None, None));
+
+    // Later clicks go through to the widget
+    tui.handle_mouse(ax, ay, Some(MouseEventKind::Up(MouseButton::Left)));
+    tui.handle_mouse(ax, ay, Some(down));
+    let (mouse, click) = widget(&tui, "vertical.a");
+    assert_eq!(mouse, Some((1, 1, down)));
+    assert_eq!(click, Some((1, 1, MouseButton::Left, 2)));
+
+    // Components can have their own policy
+    if let Some((ContainerChild::Component(c), _)) = tui.get_container_mut().search_name_mut("c") {
+        c.set_focus_policy(FocusPolicy::ClickThrough);
+    }
+    tui.handle_mouse(pos_c.x + 2, pos_c.y + 2, Some(down));
+    assert_eq!(widget(&tui, "c").0, Some((1, 1, down)));
+
+    // Hovering focuses components that follow the mouse
+    tui.set_focus_policy(Some(FocusPolicy::FollowMouse));
+    tui.handle_mouse(ax, ay, Some(MouseEventKind::Moved));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.a"))
+    );
+    let (_, pos_b) = tui.get_container().search_name("vertical.b").unwrap();
+    tui.handle_mouse(pos_b.x + 2, pos_b.y + 2, Some(MouseEventKind::Moved));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.b"))
+    );
+    tui.handle_mouse(pos_c.x + 2, pos_c.y + 2, Some(MouseEventKind::Moved));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("c"))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_keyboard_resize() -> Result<(), tui_tiling::ResizeError> {
+    let mut tui = Root::new(get_tui(false)?);
+    tui.resize(32, 16)?;
+    let size = |tui: &Root, path: &str| {
+        let (child, _) = tui.get_container().search_name(path).unwrap();
+        (child.as_base().get_width(), child.as_base().get_height())
+    };
+
+    // Growing moves the border outwards, taking space from the neighbour
+    tui.focus_name("c", Focus::PartialFocus);
+    assert!(tui.resize_focused(Border::Left, 2));
+    assert_eq!(size(&tui, "vertical"), (14, 16));
+    assert_eq!(size(&tui, "c"), (18, 16));
+
+    // Without a neighbour across the border the opposite border is used
+    assert!(tui.run_command(RootCommand::Resize(Border::Right, 1)));
+    assert_eq!(size(&tui, "c"), (19, 16));
+
+    // Resizing stops at the maximum size
+    if let Some((ContainerChild::Component(c), _)) = tui.get_container_mut().search_name_mut("c") {
+        c.set_max_width(Some(20));
+    }
+    assert!(tui.resize_focused(Border::Left, 5));
+    assert_eq!(size(&tui, "c"), (20, 16));
+    assert!(!tui.resize_focused(Border::Left, 1));
+
+    // Fixed size neighbours cannot be resized
+    tui.focus_name("vertical.a", Focus::PartialFocus);
+    assert!(tui.resize_focused(Border::Top, 1));
+    assert_eq!(size(&tui, "vertical.fixed"), (12, 6));
+    assert_eq!(size(&tui, "vertical.a"), (12, 6));
+    assert_eq!(size(&tui, "vertical.b"), (12, 4));
+
+    // Arrow keys move the nearest border in resize mode
+    tui.focus_name("vertical.b", Focus::PartialFocus);
+    assert!(tui.run_command(RootCommand::ResizeMode));
+    assert_eq!(tui.get_status(), Some(String::from("RESIZE")));
+    tui.handle_key(key(KeyCode::Up));
+    assert_eq!(size(&tui, "vertical.b"), (12, 5));
+    tui.handle_key(key(KeyCode::Down));
+    tui.handle_key(key(KeyCode::Down));
+    assert_eq!(size(&tui, "vertical.b"), (12, 3));
+    tui.handle_key(key(KeyCode::Esc));
+    assert!(!tui.is_resize_mode());
+    assert_eq!(tui.get_container().get_focus(), Focus::PartialFocus);
+
+    // Dragging borders with the mouse also respects the sizes
+    let (_, pos_c) = tui.get_container().search_name("c").unwrap();
+    let drag = MouseEventKind::Drag(MouseButton::Left);
+    tui.handle_mouse(pos_c.x, 5, Some(MouseEventKind::Down(MouseButton::Left)));
+    tui.handle_mouse(pos_c.x - 2, 5, Some(drag));
+    assert_eq!(size(&tui, "c"), (20, 16));
+    tui.handle_mouse(pos_c.x + 3, 5, Some(drag));
+    assert_eq!(size(&tui, "c"), (17, 16));
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_adjustable_fixed() -> Result<(), tui_tiling::ResizeError> {
+    let widget = || Box::new(ComponentWidgetSimple::new());
+    let mut tui = ContainerList::new(String::from("vertical"), Direction::Vertical, true, 0, 0)
+        .from_children(vec![
+            ContainerChild::from(
+                Component::new(String::from("header"), 1, widget())
+                    .fixed_height(Some(6))
+                    .adjustable(true)
+                    .max_height(Some(8)),
+            ),
+            ContainerChild::from(Component::new(String::from("a"), 1, widget())),
+            ContainerChild::from(Component::new(String::from("b"), 1, widget())),
+            ContainerChild::from(
+                Component::new(String::from("footer"), 1, widget()).fixed_height(Some(4)),
+            ),
+        ])?;
+    tui.resize(20, 20)?;
+    let height = |tui: &ContainerList, path: &str| {
+        let (child, _) = tui.as_container().search_name(path).unwrap();
+        child.as_base().get_height()
+    };
+    assert_eq!(height(&tui, "a"), 5);
+    let down = MouseEventKind::Down(MouseButton::Left);
+    let drag = MouseEventKind::Drag(MouseButton::Left);
+
+    // Dragging an adjustable component changes its fixed size
+    tui.handle_mouse(5, 5, Some(down));
+    tui.handle_mouse(5, 7, Some(drag));
+    assert_eq!(height(&tui, "header"), 8);
+    assert_eq!(height(&tui, "a"), 3);
+    let Some((ContainerChild::Component(header), _)) = tui.as_container().search_name("header")
+    else {
+        panic!("header is not a component");
+    };
+    assert!(header.is_fixed_height());
+
+    // The maximum size still applies
+    tui.handle_mouse(5, 12, Some(drag));
+    assert_eq!(height(&tui, "header"), 8);
+    tui.handle_mouse(5, 12, Some(MouseEventKind::Up(MouseButton::Left)));
+
+    // The ratio children absorb any later change in size
+    tui.resize(20, 30)?;
+    assert_eq!(height(&tui, "header"), 8);
+    assert_eq!(height(&tui, "footer"), 4);
+    assert_eq!(height(&tui, "a") + height(&tui, "b"), 18);
+
+    // Fixed components that are not adjustable stay the same size
+    tui.handle_mouse(5, 26, Some(down));
+    tui.handle_mouse(5, 24, Some(drag));
+    assert_eq!(height(&tui, "footer"), 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_nested_resize() -> Result<(), tui_tiling::ResizeError> {
+    let component = |name: &str| {
+        ContainerChild::from(Component::new(
+            String::from(name),
+            1,
+            Box::new(ComponentWidgetSimple::new()),
+        ))
+    };
+    let list = |name: &str, orientation, children| -> Result<_, tui_tiling::ResizeError> {
+        let list = ContainerList::new(String::from(name), orientation, true, 0, 0);
+        Ok(ContainerChild::from(
+            Box::new(list.from_children(children)?) as Box<dyn Container>,
+        ))
+    };
+    let inner = list(
+        "inner",
+        Direction::Horizontal,
+        vec![component("x"), component("y")],
+    )?;
+    let vertical = list("vertical", Direction::Vertical, vec![inner, component("b")])?;
+    let horizontal = ContainerList::new(
+        String::from("horizontal"),
+        Direction::Horizontal,
+        true,
+        0,
+        0,
+    )
+    .from_children(vec![vertical, component("c")])?;
+    let mut tui = Root::new(Box::new(horizontal));
+    tui.resize(40, 20)?;
+    let size = |tui: &Root, path: &str| {
+        let (child, _) = tui.get_container().search_name(path).unwrap();
+        (child.as_base().get_width(), child.as_base().get_height())
+    };
+    let down = MouseEventKind::Down(MouseButton::Left);
+    let up = MouseEventKind::Up(MouseButton::Left);
+    let drag = MouseEventKind::Drag(MouseButton::Left);
+
+    // Borders across the orientation of a container do not move its dividers
+    tui.handle_mouse(0, 12, Some(down));
+    tui.handle_mouse(0, 14, Some(drag));
+    tui.handle_mouse(0, 14, Some(up));
+    assert_eq!(size(&tui, "vertical.b"), (20, 10));
+
+    // The outer border of a component two levels deep moves the top divider,
+    // resizing every container in between
+    tui.handle_mouse(19, 3, Some(down));
+    tui.handle_mouse(23, 3, Some(drag));
+    tui.handle_mouse(23, 3, Some(up));
+    assert_eq!(size(&tui, "vertical"), (24, 20));
+    assert_eq!(size(&tui, "vertical.inner"), (24, 10));
+    assert_eq!(size(&tui, "vertical.inner.y"), (12, 10));
+    assert_eq!(size(&tui, "c"), (16, 20));
+
+    // Corners move the dividers of both directions at once
+    tui.handle_mouse(11, 9, Some(down));
+    tui.handle_mouse(13, 11, Some(drag));
+    tui.handle_mouse(13, 11, Some(up));
+    assert_eq!(size(&tui, "vertical.inner.x"), (14, 12));
+    assert_eq!(size(&tui, "vertical.b"), (24, 8));
+
+    // Keyboard resizing uses the divider of the nearest ancestor
+    tui.focus_name("vertical.inner.y", Focus::PartialFocus);
+    assert!(tui.resize_focused(Border::Right, 2));
+    assert_eq!(size(&tui, "vertical"), (26, 20));
+    assert_eq!(size(&tui, "c"), (14, 20));
+    assert!(tui.resize_focused(Border::Bottom, 1));
+    assert_eq!(size(&tui, "vertical.b"), (26, 7));
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_splitter() -> Result<(), tui_tiling::ResizeError> {
+    let component = |name: &str| {
+        ContainerChild::from(Component::new(
+            String::from(name),
+            0,
+            Box::new(ComponentWidgetSimple::new()),
+        ))
+    };
+    let horizontal = ContainerList::new(
+        String::from("horizontal"),
+        Direction::Horizontal,
+        true,
+        0,
+        0,
+    )
+    .splitter(Some('│'))?
+    .from_children(vec![component("a"), component("b"), component("c")])?;
+    let mut tui = Root::new(Box::new(horizontal));
+    tui.resize(32, 8)?;
+    let rect = |tui: &Root, path: &str| {
+        let (child, pos) = tui.get_container().search_name(path).unwrap();
+        (pos.x, child.as_base().get_width())
+    };
+
+    // Splitters take up a cell between each child
+    assert_eq!(rect(&tui, "a"), (0, 10));
+    assert_eq!(rect(&tui, "b"), (11, 10));
+    assert_eq!(rect(&tui, "c"), (22, 10));
+    let buffer = render_helper(&mut tui);
+    assert_eq!(buffer.get(10, 3).symbol, "│");
+    assert_eq!(buffer.get(21, 3).symbol, "│");
+    assert_eq!(buffer.get(10, 3).fg, Color::White);
+
+    // Hovering highlights the splitter, which belongs to neither child
+    tui.handle_mouse(10, 3, Some(MouseEventKind::Moved));
+    let buffer = render_helper(&mut tui);
+    assert_eq!(buffer.get(10, 0).fg, Color::LightCyan);
+    assert_eq!(buffer.get(21, 0).fg, Color::White);
+    assert_eq!(tui.get_border(10, 3), None);
+
+    // Dragging the splitter resizes the children on either side
+    tui.handle_mouse(10, 3, Some(MouseEventKind::Down(MouseButton::Left)));
+    tui.handle_mouse(13, 3, Some(MouseEventKind::Drag(MouseButton::Left)));
+    tui.handle_mouse(13, 3, Some(MouseEventKind::Up(MouseButton::Left)));
+    assert_eq!(rect(&tui, "a"), (0, 13));
+    assert_eq!(rect(&tui, "b"), (14, 7));
+    assert_eq!(rect(&tui, "c"), (22, 10));
+
+    // Focus moves across the splitters
+    tui.focus_name("a", Focus::PartialFocus);
+    assert!(tui.run_command(RootCommand::FocusMove(Border::Right)));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("b"))
+    );
+
+    // Dragging a splitter leaves the focused child focused
+    tui.handle_mouse(21, 3, Some(MouseEventKind::Down(MouseButton::Left)));
+    tui.handle_mouse(19, 3, Some(MouseEventKind::Drag(MouseButton::Left)));
+    tui.handle_mouse(19, 3, Some(MouseEventKind::Up(MouseButton::Left)));
+    assert_eq!(rect(&tui, "b"), (14, 5));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("b"))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_layout_commands() -> Result<(), tui_tiling::ResizeError> {
+    let mut tui = Root::new(get_tui(false)?);
+    tui.resize(32, 16)?;
+    let size = |tui: &Root, path: &str| {
+        let (child, _) = tui.get_container().search_name(path).unwrap();
+        (child.as_base().get_width(), child.as_base().get_height())
+    };
+    let names = |tui: &Root, path: &str| match tui.get_container().search_name(path) {
+        Some((ContainerChild::Container(container), _)) => container
+            .get_children()
+            .iter()
+            .map(|c| c.as_base().get_name())
+            .collect::<Vec<String>>(),
+        _ => Vec::new(),
+    };
+
+    // Equalizing sets the ratio children to the same size
+    tui.focus_name("c", Focus::PartialFocus);
+    assert!(tui.resize_focused(Border::Left, 4));
+    tui.focus_name("vertical.a", Focus::PartialFocus);
+    assert!(tui.resize_focused(Border::Bottom, 2));
+    assert!(tui.run_command(RootCommand::Equalize(false)));
+    assert_eq!(size(&tui, "vertical.a"), (12, 5));
+    assert_eq!(size(&tui, "vertical.b"), (12, 5));
+    assert_eq!(size(&tui, "c"), (20, 16));
+    assert!(tui.resize_focused(Border::Bottom, 2));
+    tui.focus_name("c", Focus::PartialFocus);
+    assert!(tui.run_command(RootCommand::Equalize(true)));
+    assert_eq!(size(&tui, "vertical"), (16, 16));
+    assert_eq!(size(&tui, "vertical.fixed"), (16, 6));
+    assert_eq!(size(&tui, "vertical.a"), (16, 5));
+
+    // Rotating moves the children along, and back again when reversed
+    tui.focus_name("vertical.a", Focus::PartialFocus);
+    assert!(tui.run_command(RootCommand::Rotate(false)));
+    assert_eq!(names(&tui, "vertical"), vec!["b", "fixed", "a"]);
+    let (_, pos) = tui.get_container().search_name("vertical.fixed").unwrap();
+    assert_eq!(pos.y, 5);
+    assert_eq!(size(&tui, "vertical.fixed"), (16, 6));
+    assert!(tui.run_command(RootCommand::Rotate(true)));
+    assert_eq!(names(&tui, "vertical"), vec!["fixed", "a", "b"]);
+
+    // Swapping exchanges the components and their sizes, keeping focus
+    assert!(tui.run_command(RootCommand::Swap(Border::Right)));
+    assert_eq!(names(&tui, "vertical"), vec!["fixed", "c", "b"]);
+    assert_eq!(size(&tui, "vertical.c"), (16, 5));
+    assert_eq!(size(&tui, "a"), (16, 16));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("a"))
+    );
+    tui.focus_name("vertical.c", Focus::PartialFocus);
+    assert!(tui.run_command(RootCommand::Swap(Border::Right)));
+    assert_eq!(names(&tui, "vertical"), vec!["fixed", "a", "b"]);
+
+    // Fixed size components cannot take the size of the other
+    tui.focus_name("vertical.fixed", Focus::PartialFocus);
+    assert!(!tui.run_command(RootCommand::Swap(Border::Right)));
+    assert_eq!(size(&tui, "vertical.fixed"), (16, 6));
+    assert_eq!(size(&tui, "c"), (16, 16));
+
+    // Flipping is rolled back if the children do not fit
+    tui.focus_name("c", Focus::PartialFocus);
+    assert!(!tui.run_command(RootCommand::Flip));
+    assert_eq!(size(&tui, "vertical"), (16, 16));
+    assert_eq!(size(&tui, "vertical.a"), (16, 5));
+    tui.resize(32, 30)?;
+    assert!(tui.run_command(RootCommand::Flip));
+    assert_eq!(size(&tui, "vertical"), (32, 15));
+    assert_eq!(size(&tui, "c"), (32, 15));
+    assert!(tui.flip());
+    assert_eq!(size(&tui, "vertical"), (16, 30));
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_drag_panes() -> Result<(), tui_tiling::ResizeError> {
+    assert!(!Root::new(get_tui(false)?).get_drag_panes());
+    let mut tui = Root::new(get_tui(false)?).drag_panes(true);
+    tui.resize(32, 30)?;
+    let size = |tui: &Root, path: &str| {
+        let (child, _) = tui.get_container().search_name(path).unwrap();
+        (child.as_base().get_width(), child.as_base().get_height())
+    };
+    let down = MouseEventKind::Down(MouseButton::Left);
+    let drag = MouseEventKind::Drag(MouseButton::Left);
+    let up = MouseEventKind::Up(MouseButton::Left);
+
+    // Clicking a title without dragging leaves the layout alone
+    tui.handle_mouse(17, 0, Some(down));
+    tui.handle_mouse(17, 0, Some(up));
+    assert_eq!(tui.get_drop_target(), None);
+    assert_eq!(size(&tui, "c"), (16, 30));
+
+    // Dragging a title over the edge of another component previews the drop
+    tui.handle_mouse(17, 0, Some(down));
+    tui.handle_mouse(20, 5, Some(drag));
+    assert_eq!(tui.get_drop_target(), None);
+    tui.handle_mouse(8, 17, Some(drag));
+    assert_eq!(
+        tui.get_drop_target(),
+        Some((
+            String::from("c"),
+            String::from("vertical.a"),
+            DropZone::Edge(Border::Bottom)
+        ))
+    );
+    let buffer = render_helper(&mut tui);
+    assert_eq!(buffer.get(8, 17).bg, Color::Blue);
+    assert_ne!(buffer.get(8, 7).bg, Color::Blue);
+
+    // Dropping splits the target, nesting a container for both
+    tui.handle_mouse(8, 17, Some(up));
+    assert_eq!(tui.get_drop_target(), None);
+    assert_eq!(size(&tui, "vertical"), (32, 30));
+    assert_eq!(size(&tui, "vertical.a_split.a"), (32, 6));
+    assert_eq!(size(&tui, "vertical.a_split.c"), (32, 6));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("vertical.a_split.c"))
+    );
+
+    // Dropping in the center swaps the components
+    tui.handle_mouse(1, 18, Some(down));
+    tui.handle_mouse(16, 9, Some(drag));
+    tui.handle_mouse(16, 9, Some(up));
+    assert_eq!(size(&tui, "vertical.a_split.b"), (32, 6));
+    assert_eq!(size(&tui, "vertical.a"), (32, 12));
+
+    // Titles are only borders once dragging is turned off
+    tui.set_drag_panes(false);
+    tui.handle_mouse(1, 18, Some(down));
+    tui.handle_mouse(16, 9, Some(drag));
+    tui.handle_mouse(16, 9, Some(up));
+    assert!(tui.get_container().search_name("vertical.a").is_some());
+    assert!(tui
+        .get_container()
+        .search_name("vertical.a_split.b")
+        .is_some());
+
+    // Components are only moved if they fit in the space of the target
+    let target = "vertical.a_split.c";
+    assert!(!tui.move_pane("vertical.fixed", target, DropZone::Edge(Border::Top)));
+    assert!(!tui.move_pane("vertical.fixed", target, DropZone::Edge(Border::Right)));
+    assert!(tui.move_pane("vertical.a", target, DropZone::Edge(Border::Left)));
+    assert_eq!(size(&tui, "vertical.a_split.c_split.a"), (16, 12));
+    assert_eq!(size(&tui, "vertical.a_split.c_split.c"), (16, 12));
+
+    // A target too small to split leaves both components in place
+    tui.get_container_mut()
+        .search_name_mut("vertical.a_split.c_split.c")
+        .unwrap()
+        .0
+        .unwrap_component_mut()
+        .set_min_width(Some(20));
+    assert!(!tui.move_pane(
+        "vertical.a_split.c_split.c",
+        "vertical.a_split.b",
+        DropZone::Edge(Border::Right)
+    ));
+    assert_eq!(size(&tui, "vertical.a_split.c_split.c"), (16, 12));
+
+    // As does a split that fails to size, putting the component back
+    let mut tui = Root::new(Box::new(
+        ContainerList::new(String::from("list"), Direction::Horizontal, true, 0, 0).from_children(
+            vec![
+                ContainerChild::from(
+                    Component::new(
+                        String::from("a"),
+                        1,
+                        Box::new(TestComponentWidget::new(false)),
+                    )
+                    .fixed_height(Some(10))
+                    .adjustable(true),
+                ),
+                ContainerChild::from(Component::new(
+                    String::from("b"),
+                    1,
+                    Box::new(TestComponentWidget::new(false)),
+                )),
+            ],
+        )?,
+    ));
+    tui.resize(30, 10)?;
+    let children = |tui: &Root| {
+        tui.get_container()
+            .get_children()
+            .iter()
+            .map(|c| (c.as_base().get_name(), c.as_base().get_width()))
+            .collect::<Vec<(String, u16)>>()
+    };
+    let before = children(&tui);
+    assert!(!tui.move_pane("a", "b", DropZone::Edge(Border::Bottom)));
+    assert_eq!(children(&tui), before);
+
+    // Splits are named apart from siblings that already have the name
+    let component = |name: &str| {
+        ContainerChild::from(Component::new(
+            String::from(name),
+            1,
+            Box::new(TestComponentWidget::new(false)),
+        ))
+    };
+    let mut tui = Root::new(Box::new(
+        ContainerList::new(String::from("list"), Direction::Horizontal, false, 0, 0)
+            .from_children(vec![
+                component("a"),
+                component("b"),
+                ContainerChild::from(
+                    ContainerList::new(String::from("a_split"), Direction::Vertical, false, 0, 0)
+                        .from_children(vec![component("z")])?,
+                ),
+            ])?,
+    ));
+    tui.resize(30, 10)?;
+    assert!(tui.move_pane("b", "a", DropZone::Edge(Border::Bottom)));
+    assert!(tui.get_container().search_name("a_split2.a").is_some());
+    assert!(tui.get_container().search_name("a_split2.b").is_some());
+    assert!(tui.get_container().search_name("a_split.z").is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_tui_auto_layout() -> Result<(), tui_tiling::ResizeError> {
+    let component = |name: &str| {
+        Component::new(
+            String::from(name),
+            1,
+            Box::new(ComponentWidgetSimple::new()),
+        )
+    };
+    let mut auto = ContainerAuto::new(String::from("auto"), AutoLayout::MasterStack, 0, 0)
+        .from_children(vec![
+            ContainerChild::from(component("a")),
+            ContainerChild::from(component("b")),
+            ContainerChild::from(component("c")),
+        ])?;
+    auto.resize(40, 20)?;
+    let rect = |container: &dyn Container, path: &str| {
+        let (child, pos) = container.search_name(path).unwrap();
+        let base = child.as_base();
+        (pos.x, pos.y, base.get_width(), base.get_height())
+    };
+
+    // The first child is the master with the rest stacked next to it
+    assert_eq!(rect(&auto, "a"), (0, 0, 20, 20));
+    assert_eq!(rect(&auto, "b"), (20, 0, 20, 10));
+    assert_eq!(rect(&auto, "c"), (20, 10, 20, 10));
+
+    // Adding and removing children arranges them again
+    auto.add_component(component("d"))?;
+    assert_eq!(rect(&auto, "d"), (20, 12, 20, 8));
+    assert!(auto.remove_child("b").is_some());
+    assert_eq!(rect(&auto, "c"), (20, 0, 20, 10));
+    assert_eq!(rect(&auto, "d"), (20, 10, 20, 10));
+
+    // The border between the master and the stack can be moved
+    assert!(auto.resize_child("a", Border::Right, 4));
+    assert_eq!(rect(&auto, "a"), (0, 0, 24, 20));
+    assert!(auto.resize_child("d", Border::Left, 2));
+    assert_eq!(rect(&auto, "a"), (0, 0, 22, 20));
+    assert!(!auto.resize_child("a", Border::Bottom, 2));
+    auto.equalize(false)?;
+    assert_eq!(rect(&auto, "a"), (0, 0, 20, 20));
+
+    // Each layout arranges the same children differently
+    auto.set_layout(AutoLayout::EvenHorizontal)?;
+    assert_eq!(rect(&auto, "d"), (26, 0, 14, 20));
+    auto.set_layout(AutoLayout::EvenVertical)?;
+    assert_eq!(rect(&auto, "d"), (0, 12, 40, 8));
+    auto.set_layout(AutoLayout::Tiled)?;
+    assert_eq!(rect(&auto, "c"), (20, 0, 20, 10));
+    assert_eq!(rect(&auto, "d"), (0, 10, 40, 10));
+    auto.set_layout(AutoLayout::Spiral)?;
+    assert_eq!(rect(&auto, "a"), (0, 0, 20, 20));
+    assert_eq!(rect(&auto, "c"), (20, 0, 20, 10));
+    assert_eq!(rect(&auto, "d"), (20, 10, 20, 10));
+    auto.flip()?;
+    assert_eq!(rect(&auto, "a"), (0, 0, 40, 10));
+    auto.flip()?;
+
+    // Layouts the children do not fit in are not used
+    let mut small = ContainerAuto::new(String::from("small"), AutoLayout::MasterStack, 0, 0)
+        .from_children(vec![
+            ContainerChild::from(component("a")),
+            ContainerChild::from(component("b")),
+            ContainerChild::from(component("c")),
+        ])?;
+    small.resize(5, 4)?;
+    assert!(small.set_layout(AutoLayout::EvenHorizontal).is_err());
+    assert_eq!(small.get_layout(), AutoLayout::MasterStack);
+    assert!(small.add_component(component("d")).is_err());
+    assert_eq!(small.get_children().len(), 3);
+
+    // Dragging the border between the master and the stack moves it
+    let mut auto = auto.layout(AutoLayout::MasterStack)?;
+    auto.handle_mouse(19, 5, Some(MouseEventKind::Down(MouseButton::Left)));
+    auto.handle_mouse(24, 5, Some(MouseEventKind::Drag(MouseButton::Left)));
+    auto.handle_mouse(24, 5, Some(MouseEventKind::Up(MouseButton::Left)));
+    assert_eq!(rect(&auto, "a"), (0, 0, 25, 20));
+    assert_eq!(rect(&auto, "c"), (25, 0, 15, 10));
+    auto.handle_mouse(10, 5, Some(MouseEventKind::Drag(MouseButton::Left)));
+    assert_eq!(rect(&auto, "a"), (0, 0, 25, 20));
+    auto.equalize(false)?;
+
+    // Fixed children keep their size and the rest share what is left
+    let mut fixed = ContainerAuto::new(String::from("fixed"), AutoLayout::EvenVertical, 0, 0)
+        .from_children(vec![
+            ContainerChild::from(component("a").fixed_height(Some(4))),
+            ContainerChild::from(component("b")),
+            ContainerChild::from(component("c")),
+        ])?;
+    fixed.resize(40, 20)?;
+    assert_eq!(rect(&fixed, "a"), (0, 0, 40, 4));
+    assert_eq!(rect(&fixed, "b"), (0, 4, 40, 8));
+    assert_eq!(rect(&fixed, "c"), (0, 12, 40, 8));
+    assert_eq!(fixed.get_size_range(Direction::Vertical), (8, u16::MAX));
+    assert!(fixed.set_layout(AutoLayout::EvenHorizontal).is_err());
+    assert!(fixed.resize(40, 3).is_err());
+    assert_eq!(rect(&fixed, "b"), (0, 4, 40, 8));
+
+    // The root cycles through the layouts and moves focus between children
+    let mut tui = Root::new(Box::new(auto));
+    tui.resize(40, 20)?;
+    tui.focus_name("a", Focus::PartialFocus);
+    assert!(tui.run_command(RootCommand::NextLayout));
+    assert_eq!(rect(tui.get_container(), "a"), (0, 0, 13, 20));
+    assert!(tui.run_command(RootCommand::FocusMove(Border::Right)));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("c"))
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_tui_interactive() -> Result<(), std::io::Error> {
     tui_main()
 }
+
+#[test]
+fn test_tui_scroll_container() -> Result<(), tui_tiling::ResizeError> {
+    let fields = (0..40)
+        .map(|i| {
+            ContainerChild::from(
+                Component::new(
+                    format!("field{i}"),
+                    1,
+                    Box::new(ComponentWidgetSimple::new()),
+                )
+                .fixed_height(Some(3)),
+            )
+        })
+        .collect::<Vec<ContainerChild>>();
+    let form = ContainerList::new(String::from("form"), Direction::Vertical, false, 0, 0)
+        .menu_items(vec![MenuItem::new("Reset", "reset")])
+        .from_children(fields)?;
+
+    // The fields do not fit without scrolling
+    let mut small = ContainerList::new(String::from("form"), Direction::Vertical, false, 0, 0)
+        .from_children(vec![ContainerChild::from(
+            Component::new(String::from("a"), 1, Box::new(ComponentWidgetSimple::new()))
+                .fixed_height(Some(30)),
+        )])?;
+    assert!(small.resize(80, 24).is_err());
+
+    // The child is laid out in content as large as it needs, next to a scrollbar
+    let mut scroll = ContainerScroll::new(String::from("scroll"), 0, 0)
+        .from_child(ContainerChild::from(form))?;
+    scroll.resize(80, 24)?;
+    assert_eq!(scroll.get_content_size(), (79, 120));
+    assert_eq!(scroll.get_offset(), (0, 0));
+    let buffer = render_helper(&mut scroll);
+    assert_eq!(buffer.get(79, 0).symbol, "█");
+    assert_eq!(buffer.get(79, 23).symbol, "│");
+
+    // The wheel and the page keys scroll the content
+    scroll.handle_scroll(10, 10, MouseEventKind::ScrollDown, KeyModifiers::NONE);
+    assert_eq!(scroll.get_offset(), (0, 3));
+    scroll.handle_scroll(10, 10, MouseEventKind::ScrollUp, KeyModifiers::NONE);
+    scroll.handle_scroll(10, 10, MouseEventKind::ScrollUp, KeyModifiers::NONE);
+    assert_eq!(scroll.get_offset(), (0, 0));
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    assert_eq!(
+        scroll.handle_key(key(KeyCode::PageDown)),
+        EventResult::Redraw
+    );
+    assert_eq!(scroll.get_offset(), (0, 24));
+    assert_eq!(scroll.handle_key(key(KeyCode::End)), EventResult::Redraw);
+    assert_eq!(scroll.get_offset(), (0, 96));
+    assert_eq!(scroll.handle_key(key(KeyCode::End)), EventResult::Ignored);
+    let buffer = render_helper(&mut scroll);
+    assert_eq!(buffer.get(79, 23).symbol, "█");
+
+    // Dragging the scrollbar moves the content with it
+    scroll.handle_mouse(79, 0, Some(MouseEventKind::Down(MouseButton::Left)));
+    assert_eq!(scroll.get_offset(), (0, 0));
+    scroll.handle_mouse(79, 11, Some(MouseEventKind::Drag(MouseButton::Left)));
+    assert_eq!(scroll.get_offset(), (0, 45));
+    scroll.handle_mouse(79, 11, Some(MouseEventKind::Up(MouseButton::Left)));
+
+    // Mouse events reach the child under the mouse in the scrolled content
+    scroll.handle_mouse(10, 4, Some(MouseEventKind::Down(MouseButton::Left)));
+    scroll.handle_mouse(10, 4, Some(MouseEventKind::Up(MouseButton::Left)));
+    assert_eq!(
+        scroll.as_container().search_focused_name(),
+        Some(String::from("form.field16"))
+    );
+
+    // Searching finds the components where they are shown in the window
+    assert!(scroll.scroll_by(0, 1));
+    let (_, pos) = scroll.as_container().search_name("form.field16").unwrap();
+    assert_eq!(pos, ComponentPos { x: 0, y: 2 });
+    let (component, _) = scroll
+        .as_container()
+        .search_position(ComponentPos { x: 5, y: 4 })
+        .unwrap();
+    assert_eq!(component.get_name(), "field16");
+    let views = scroll.as_container().search_component_views();
+    assert_eq!(views.len(), 9);
+    assert_eq!(views[0].0, "form.field15");
+    assert_eq!(views[0].2, ComponentPos { x: 0, y: 0 });
+    assert_eq!(views[0].3, Rect::new(0, 1, 79, 2));
+    assert_eq!(views[8].3, Rect::new(0, 0, 79, 1));
+
+    // The menu items and children of the form are reached through the container
+    assert_eq!(scroll.get_menu_items().len(), 1);
+    assert!(scroll.remove_child("field39").is_some());
+    assert_eq!(scroll.get_content_size(), (79, 117));
+
+    // Content larger than 65535 cells cannot be drawn offscreen
+    assert!(scroll.set_virtual_size(None, Some(1000)).is_err());
+    assert_eq!(scroll.get_virtual_size(), (None, None));
+
+    // Focus moved outside of the window scrolls it into view on the next update
+    let mut tui = Root::new(Box::new(scroll));
+    tui.resize(80, 24)?;
+    assert!(tui.focus_name("form.field38", Focus::PartialFocus));
+    tui.handle_update();
+    let buffer = render_helper(&mut tui);
+    assert_eq!(buffer.get(79, 23).symbol, "█");
+    assert!(tui.focus_name("form.field0", Focus::PartialFocus));
+    tui.handle_update();
+    let buffer = render_helper(&mut tui);
+    assert_eq!(buffer.get(79, 0).symbol, "█");
+
+    // Quick jump labels cover the part of each component in view
+    tui.start_quick_jump();
+    assert_eq!(tui.get_quick_jump_labels().len(), 8);
+    render_helper(&mut tui);
+    tui.stop_quick_jump();
+    assert!(tui.run_command(RootCommand::FocusMove(Border::Bottom)));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("form.field1"))
+    );
+
+    // Focus moves start from where a component scrolled partly out of view
+    // is, rather than from the part in view
+    let component = |name: &str| {
+        ContainerChild::from(Component::new(
+            String::from(name),
+            1,
+            Box::new(ComponentWidgetSimple::new()),
+        ))
+    };
+    let mut scroll = ContainerScroll::new(String::from("scroll"), 0, 0)
+        .from_child(component("big"))?
+        .virtual_size(None, Some(10))?;
+    scroll.resize(20, 4)?;
+    assert!(scroll.scroll_to(0, 6));
+    let list = ContainerList::new(String::from("list"), Direction::Vertical, false, 0, 0)
+        .from_children(vec![ContainerChild::from(scroll), component("below")])?;
+    let mut tui = Root::new(Box::new(list));
+    tui.resize(20, 8)?;
+    assert!(tui.focus_name("scroll.big", Focus::PartialFocus));
+    assert!(!tui.run_command(RootCommand::FocusMove(Border::Top)));
+    assert!(tui.run_command(RootCommand::FocusMove(Border::Bottom)));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("below"))
+    );
+    Ok(())
+}
+
+struct TestChildSource {
+    len: usize,
+    created: Rc<Cell<usize>>,
+    released: Rc<Cell<usize>>,
+}
+
+impl ChildSource for TestChildSource {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get_child(&mut self, index: usize) -> ContainerChild {
+        self.created.set(self.created.get() + 1);
+        ContainerChild::from(Component::new(
+            format!("host{index}"),
+            1,
+            Box::new(ComponentWidgetSimple::new()),
+        ))
+    }
+
+    fn release_child(&mut self, _index: usize, _child: ContainerChild) {
+        self.released.set(self.released.get() + 1);
+    }
+}
+
+#[test]
+fn test_tui_virtual_container() -> Result<(), tui_tiling::ResizeError> {
+    let created = Rc::new(Cell::new(0));
+    let released = Rc::new(Cell::new(0));
+    let source = TestChildSource {
+        len: 1000,
+        created: created.clone(),
+        released: released.clone(),
+    };
+    let mut hosts = ContainerVirtual::new(
+        String::from("hosts"),
+        Direction::Vertical,
+        4,
+        Box::new(source),
+        0,
+        0,
+    );
+    let names = |hosts: &ContainerVirtual| {
+        hosts
+            .get_children()
+            .iter()
+            .map(|c| c.as_base().get_name())
+            .collect::<Vec<String>>()
+    };
+
+    // Only the children in view are created, next to the scrollbar
+    hosts.resize(40, 22)?;
+    assert_eq!(created.get(), 5);
+    assert_eq!(names(&hosts), ["host0", "host1", "host2", "host3", "host4"]);
+    let (child, pos) = hosts.as_container().search_name("host4").unwrap();
+    assert_eq!((pos.y, child.as_base().get_width()), (16, 39));
+    let buffer = render_helper(&mut hosts);
+    assert_eq!(buffer.get(39, 0).symbol, "█");
+    assert_eq!(buffer.get(39, 21).symbol, "│");
+
+    // Scrolling keeps the children still in view and releases the rest
+    hosts.handle_scroll(5, 5, MouseEventKind::ScrollDown, KeyModifiers::NONE);
+    assert_eq!(hosts.get_first(), 1);
+    assert_eq!((created.get(), released.get()), (6, 1));
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    assert_eq!(hosts.handle_key(key(KeyCode::End)), EventResult::Redraw);
+    assert_eq!(hosts.get_first(), 995);
+    assert_eq!(names(&hosts)[4], "host999");
+    assert_eq!((created.get(), released.get()), (11, 6));
+    let buffer = render_helper(&mut hosts);
+    assert_eq!(buffer.get(39, 21).symbol, "█");
+    assert_eq!(hosts.handle_key(key(KeyCode::PageUp)), EventResult::Redraw);
+    assert_eq!(hosts.get_first(), 990);
+
+    // Dragging the scrollbar jumps through the children
+    hosts.handle_mouse(39, 0, Some(MouseEventKind::Down(MouseButton::Left)));
+    assert_eq!(hosts.get_first(), 0);
+    hosts.handle_mouse(39, 21, Some(MouseEventKind::Drag(MouseButton::Left)));
+    hosts.handle_mouse(39, 21, Some(MouseEventKind::Up(MouseButton::Left)));
+    assert_eq!(hosts.get_first(), 995);
+    assert_eq!(hosts.handle_key(key(KeyCode::Home)), EventResult::Redraw);
+
+    // Moving focus past the last child in view scrolls to the next one
+    let mut tui = Root::new(Box::new(hosts));
+    tui.resize(40, 22)?;
+    assert!(tui.focus_name("host4", Focus::PartialFocus));
+    assert!(tui.run_command(RootCommand::FocusMove(Border::Bottom)));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("host5"))
+    );
+    let (_, pos) = tui.get_container().search_name("host5").unwrap();
+    assert_eq!(pos.y, 16);
+    assert!(tui.get_container().search_name("host0").is_none());
+    assert!(tui.run_command(RootCommand::FocusMove(Border::Top)));
+    assert!(tui.run_command(RootCommand::FocusMove(Border::Top)));
+    assert!(tui.run_command(RootCommand::FocusMove(Border::Top)));
+    assert!(tui.run_command(RootCommand::FocusMove(Border::Top)));
+    assert!(tui.run_command(RootCommand::FocusMove(Border::Top)));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("host0"))
+    );
+
+    // Moving focus past the first or last child keeps it where it was
+    assert!(!tui.run_command(RootCommand::FocusMove(Border::Top)));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("host0"))
+    );
+    tui.get_container_mut().handle_key(key(KeyCode::End));
+    assert!(tui.focus_name("host999", Focus::PartialFocus));
+    assert!(!tui.run_command(RootCommand::FocusMove(Border::Bottom)));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("host999"))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_tui_accordion() -> Result<(), tui_tiling::ResizeError> {
+    let component = |name: &str| {
+        ContainerChild::from(Component::new(
+            String::from(name),
+            1,
+            Box::new(ComponentWidgetSimple::new()),
+        ))
+    };
+    let mut accordion =
+        ContainerAccordion::new(String::from("tools"), 0, 0).from_children(vec![
+            component("files"),
+            component("search"),
+            component("git"),
+        ])?;
+    accordion.resize(30, 20)?;
+    let rect = |container: &dyn Container, path: &str| {
+        let (child, pos) = container.search_name(path).unwrap();
+        let base = child.as_base();
+        (pos.y, base.get_width(), base.get_height())
+    };
+
+    // The first section is expanded with the others collapsed below it
+    assert_eq!(accordion.get_expanded(), ["files"]);
+    assert_eq!(rect(&accordion, "files"), (1, 30, 17));
+    let buffer = render_helper(&mut accordion);
+    assert_eq!(buffer.get(0, 0).symbol, "▼");
+    assert_eq!(buffer.get(0, 18).symbol, "▶");
+    assert_eq!(buffer.get(2, 19).symbol, "g");
+
+    // Clicking a title expands its section and collapses the rest
+    accordion.handle_mouse(10, 19, Some(MouseEventKind::Down(MouseButton::Left)));
+    assert_eq!(accordion.get_expanded(), ["git"]);
+    assert_eq!(rect(&accordion, "git"), (3, 30, 17));
+    accordion.handle_mouse(10, 19, Some(MouseEventKind::Up(MouseButton::Left)));
+
+    // More sections can be expanded at once, collapsing the oldest first
+    accordion.set_max_expanded(2)?;
+    assert!(accordion.expand("files")?);
+    assert!(!accordion.expand("files")?);
+    assert_eq!(rect(&accordion, "files"), (1, 30, 8));
+    assert_eq!(rect(&accordion, "git"), (11, 30, 9));
+    assert!(accordion.expand("search")?);
+    assert_eq!(accordion.get_expanded(), ["files", "search"]);
+    assert!(accordion.collapse("files")?);
+    assert!(!accordion.collapse("search")?);
+    accordion.set_max_expanded(1)?;
+
+    // Enter in partial focus expands a collapsed section
+    let mut tui = Root::new(Box::new(accordion));
+    tui.resize(30, 20)?;
+    assert!(tui.focus_name("search", Focus::PartialFocus));
+    assert!(tui.run_command(RootCommand::FocusMove(Border::Bottom)));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("git"))
+    );
+    let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+    tui.get_container_mut().handle_key(enter);
+    assert_eq!(rect(tui.get_container(), "git"), (3, 30, 17));
+    assert_eq!(
+        tui.get_container().get_children()[0].as_base().get_focus(),
+        Focus::None
+    );
+
+    // Layout commands the accordion has no use for are not reported as done
+    assert!(!tui.run_command(RootCommand::Flip));
+    assert!(!tui.run_command(RootCommand::NextLayout));
+    assert_eq!(rect(tui.get_container(), "git"), (3, 30, 17));
+
+    // Collapsed sections are out of reach of quick jump and the first focus
+    assert_eq!(
+        tui.get_container().search_focusable().unwrap().0.get_name(),
+        "git"
+    );
+    tui.start_quick_jump();
+    let labels = tui.get_quick_jump_labels();
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].1, "git");
+    tui.stop_quick_jump();
+
+    // Removing the expanded section expands its neighbour
+    assert!(tui.get_container_mut().remove_child("git").is_some());
+    assert_eq!(rect(tui.get_container(), "search"), (2, 30, 18));
+    Ok(())
+}
+
+#[test]
+fn test_tui_workspaces() -> Result<(), tui_tiling::ResizeError> {
+    let component = |name: &str| {
+        ContainerChild::from(Component::new(
+            String::from(name),
+            1,
+            Box::new(TestComponentWidget::new(false)),
+        ))
+    };
+    let logs = ContainerList::new(String::from("logs"), Direction::Vertical, false, 0, 0)
+        .from_children(vec![component("log")])?;
+    let metrics = ContainerAuto::new(String::from("metrics"), AutoLayout::EvenHorizontal, 0, 0)
+        .from_children(vec![component("cpu"), component("mem")])?;
+    let mut tui = Root::new(Box::new(logs))
+        .workspace("metrics", Box::new(metrics))
+        .show_workspaces(true)?;
+    tui.resize(40, 21)?;
+    tui.set_prefix_key(Some(KeyBinding::new(
+        KeyCode::Char('b'),
+        KeyModifiers::CONTROL,
+    )));
+    let size = |container: &dyn Container, path: &str| {
+        let (child, _) = container.search_name(path).unwrap();
+        (child.as_base().get_width(), child.as_base().get_height())
+    };
+    let key_last = |tui: &Root, index: usize, path: &str| {
+        tui.get_workspace_container(index)
+            .unwrap()
+            .search_name_widget::<TestComponentWidget>(path)
+            .unwrap()
+            .get_key_last()
+    };
+
+    // The bar takes the bottom row from the active workspace
+    assert_eq!(tui.get_workspaces(), ["logs", "metrics"]);
+    assert_eq!(tui.get_workspace(), 0);
+    assert_eq!(tui.get_height(), 21);
+    assert_eq!(size(tui.get_container(), "log"), (40, 20));
+    let buffer = render_helper(&mut tui);
+    assert_eq!(buffer.get(1, 20).symbol, "0");
+    assert_eq!(buffer.get(1, 20).bg, Color::Green);
+    assert_eq!(buffer.get(9, 20).bg, Color::DarkGray);
+
+    // Terminal focus reaches the inactive workspaces too
+    let terminal_focused = |tui: &Root| {
+        let (cpu, _) = tui
+            .get_workspace_container(1)
+            .unwrap()
+            .search_name("cpu")
+            .unwrap();
+        cpu.unwrap_component().is_terminal_focused()
+    };
+    tui.handle_terminal_focus(false);
+    assert!(!terminal_focused(&tui));
+    tui.handle_terminal_focus(true);
+    assert!(terminal_focused(&tui));
+
+    // Switching by name resizes the workspace and only sends it input
+    tui.focus_name("log", Focus::Focus);
+    assert!(tui.switch_workspace_name("metrics"));
+    assert!(!tui.switch_workspace_name("metrics"));
+    assert_eq!(tui.get_container().get_name(), "metrics");
+    assert_eq!(size(tui.get_container(), "cpu"), (20, 20));
+    assert_eq!(tui.get_container().search_focused_name(), None);
+    tui.focus_name("mem", Focus::Focus);
+    let key = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+    tui.handle_key(key);
+    assert_eq!(key_last(&tui, 1, "mem"), Some(key));
+    assert_eq!(key_last(&tui, 0, "log"), None);
+
+    // The prefix bindings switch by number, keeping the focus of each
+    tui.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::CONTROL));
+    tui.handle_key(KeyEvent::new(KeyCode::Char('0'), KeyModifiers::NONE));
+    assert_eq!(tui.get_workspace(), 0);
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("log"))
+    );
+    assert!(tui.run_command(RootCommand::NextWorkspace(false)));
+    assert_eq!(
+        tui.get_container().search_focused_name(),
+        Some(String::from("mem"))
+    );
+
+    // Workspaces are resized when shown after the root changes size
+    tui.resize(50, 31)?;
+    assert!(tui.run_command(RootCommand::NextWorkspace(true)));
+    assert_eq!(size(tui.get_container(), "log"), (50, 30));
+
+    // Clicking a label on the bar switches to its workspace
+    tui.handle_mouse(9, 30, Some(MouseEventKind::Down(MouseButton::Left)));
+    assert_eq!(tui.get_workspace(), 1);
+    assert_eq!(size(tui.get_container(), "cpu"), (25, 30));
+
+    // Removing the active workspace shows another first
+    assert!(tui.remove_workspace(1).is_some());
+    assert!(tui.remove_workspace(0).is_none());
+    assert_eq!(tui.get_workspaces(), ["logs"]);
+    assert_eq!(tui.get_container().get_name(), "logs");
+    tui.set_show_workspaces(false)?;
+    assert_eq!(size(tui.get_container(), "log"), (50, 31));
+    Ok(())
+}